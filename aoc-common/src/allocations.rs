@@ -0,0 +1,159 @@
+// Counting what a piece of code allocates, to see whether pre-sizing buffers
+// or reusing them pays off.
+//
+// With the `count-allocations` feature, `Counting` is installed as the global
+// allocator of every binary built with this crate, the runner included. It
+// keeps a running tally in a few atomics, so it's cheap enough to leave on,
+// but it's off by default so that ordinary runs use the system allocator
+// untouched. The tally is shared by every thread: count one thing at a time.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static COUNT: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+static CURRENT: AtomicU64 = AtomicU64::new(0);
+static PEAK: AtomicU64 = AtomicU64::new(0);
+
+/// The system allocator, counting every allocation made through it.
+pub struct Counting;
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+impl Counting {
+    fn allocated(&self, size: usize) {
+        COUNT.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size as u64, Ordering::Relaxed);
+        let current = CURRENT.fetch_add(size as u64, Ordering::Relaxed) + size as u64;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn freed(&self, size: usize) {
+        CURRENT.fetch_sub(size as u64, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            self.allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            self.allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        self.freed(layout.size());
+    }
+
+    // Growing a `Vec` or `String` counts as allocating its new size, which is
+    // what pre-sizing it saves.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            self.freed(layout.size());
+            self.allocated(new_size);
+        }
+        new
+    }
+}
+
+/// Whether allocations are being counted, i.e. whether this was built with
+/// the `count-allocations` feature.
+pub fn enabled() -> bool {
+    cfg!(feature = "count-allocations")
+}
+
+/// What some code allocated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Allocations {
+    /// How many allocations it made, growing ones included.
+    pub count: u64,
+    /// How many bytes it asked for in total, freed or not.
+    pub bytes: u64,
+    /// The most it had allocated at any one time, beyond what already was.
+    pub peak: u64,
+}
+
+impl fmt::Display for Allocations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocations, {}, peak {}",
+            self.count,
+            Bytes(self.bytes),
+            Bytes(self.peak)
+        )
+    }
+}
+
+struct Bytes(u64);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            bytes if bytes < 1 << 10 => write!(f, "{} B", bytes),
+            bytes if bytes < 1 << 20 => write!(f, "{:.1} KiB", bytes as f64 / 1024.0),
+            bytes => write!(f, "{:.1} MiB", bytes as f64 / (1024.0 * 1024.0)),
+        }
+    }
+}
+
+/// Runs `f` once, returning its result along with what it allocated, or
+/// `None` if allocations aren't being counted. Whatever `f` returns is still
+/// allocated when it finishes, so it counts towards the peak.
+pub fn counted<T, F: FnOnce() -> T>(f: F) -> (T, Option<Allocations>) {
+    if !enabled() {
+        return (f(), None);
+    }
+    let (count, bytes) = (COUNT.load(Ordering::Relaxed), BYTES.load(Ordering::Relaxed));
+    let current = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(current, Ordering::Relaxed);
+    let result = f();
+    let allocations = Allocations {
+        count: COUNT.load(Ordering::Relaxed) - count,
+        bytes: BYTES.load(Ordering::Relaxed) - bytes,
+        peak: PEAK.load(Ordering::Relaxed).saturating_sub(current),
+    };
+    (result, Some(allocations))
+}
+
+#[test]
+fn test_allocations() {
+    let allocations = Allocations {
+        count: 3,
+        bytes: 1536,
+        peak: 1000,
+    };
+    assert_eq!(
+        "3 allocations, 1.5 KiB, peak 1000 B",
+        allocations.to_string()
+    );
+    assert_eq!("2.0 MiB", Bytes(2 << 20).to_string());
+
+    let (sum, allocations) = counted(|| {
+        let mut numbers = Vec::with_capacity(100);
+        numbers.extend(0..100u64);
+        numbers.iter().sum::<u64>()
+    });
+    assert_eq!(4950, sum);
+    match allocations {
+        // Other tests run alongside this one, and allocate too.
+        Some(allocations) => {
+            assert!(allocations.count >= 1);
+            assert!(allocations.bytes >= 800);
+        }
+        None => assert!(!enabled()),
+    }
+}
@@ -0,0 +1,54 @@
+// What a solution hands back for each part.
+use std::convert::TryFrom;
+use std::fmt;
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    Text(String),
+    /// The author hasn't solved this part, or the input has no answer to it.
+    Unsolved,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(value) => write!(f, "{}", value),
+            Answer::Text(value) => f.write_str(value),
+            Answer::Unsolved => f.write_str("unsolved"),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(value: $int) -> Answer {
+                    Answer::Int(i64::try_from(value).expect("answer doesn't fit in an i64"))
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(i32, u32, isize, usize, u64);
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Answer {
+        Answer::Int(value)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Answer {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Answer {
+        Answer::Text(value.to_string())
+    }
+}
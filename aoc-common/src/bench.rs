@@ -0,0 +1,139 @@
+// Measuring how long a piece of code takes, reliably enough to compare.
+//
+// Code is first warmed up, which also gives an estimate of how long one call
+// takes. Calls are then timed in batches big enough for the clock to measure
+// accurately, and each batch's time per call is kept as one sample.
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// How hard to try when measuring.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+    /// How long to run the code before measuring it.
+    pub warm_up: Duration,
+    /// How many samples to take.
+    pub samples: usize,
+    /// Roughly how long each sample should take.
+    pub sample_time: Duration,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            warm_up: Duration::from_millis(200),
+            samples: 50,
+            sample_time: Duration::from_millis(5),
+        }
+    }
+}
+
+/// The time per call of every sample taken, sorted from fastest to slowest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stats {
+    samples: Vec<Duration>,
+    /// How many calls each sample was averaged over.
+    pub batch: u32,
+}
+
+impl Stats {
+    pub fn new(mut samples: Vec<Duration>, batch: u32) -> Stats {
+        assert!(!samples.is_empty(), "no samples to take statistics of");
+        samples.sort();
+        Stats { samples, batch }
+    }
+
+    pub fn samples(&self) -> &[Duration] {
+        &self.samples
+    }
+
+    pub fn median(&self) -> Duration {
+        median(&self.samples)
+    }
+
+    /// The median absolute deviation from the median: how far a typical
+    /// sample strays from it, without being thrown off by a few outliers.
+    pub fn spread(&self) -> Duration {
+        let median = self.median();
+        let mut deviations: Vec<_> = self
+            .samples
+            .iter()
+            .map(|sample| sample.abs_diff(median))
+            .collect();
+        deviations.sort();
+        self::median(&deviations)
+    }
+
+    pub fn min(&self) -> Duration {
+        self.samples[0]
+    }
+
+    pub fn max(&self) -> Duration {
+        self.samples[self.samples.len() - 1]
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>10.1?} ± {:<9.1?} [{:.1?} .. {:.1?}]",
+            self.median(),
+            self.spread(),
+            self.min(),
+            self.max(),
+        )
+    }
+}
+
+fn median(sorted: &[Duration]) -> Duration {
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2
+    } else {
+        sorted[mid]
+    }
+}
+
+/// Measures how long `f` takes per call.
+pub fn measure<T, F: FnMut() -> T>(config: &Config, mut f: F) -> Stats {
+    let start = Instant::now();
+    let mut calls = 0u32;
+    while calls == 0 || start.elapsed() < config.warm_up {
+        black_box(f());
+        calls += 1;
+    }
+    let per_call = start.elapsed() / calls;
+
+    let batch = if per_call.is_zero() {
+        1000
+    } else {
+        (config.sample_time.as_nanos() / per_call.as_nanos()).clamp(1, u32::MAX as u128) as u32
+    };
+    let samples = (0..config.samples.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..batch {
+                black_box(f());
+            }
+            start.elapsed() / batch
+        })
+        .collect();
+    Stats::new(samples, batch)
+}
+
+#[test]
+fn test_stats() {
+    let ms = Duration::from_millis;
+    let stats = Stats::new(vec![ms(5), ms(1), ms(3), ms(2), ms(100)], 1);
+    assert_eq!(ms(3), stats.median());
+    assert_eq!(ms(2), stats.spread());
+    assert_eq!((ms(1), ms(100)), (stats.min(), stats.max()));
+
+    let config = Config {
+        warm_up: Duration::from_millis(1),
+        samples: 3,
+        sample_time: Duration::from_micros(100),
+    };
+    assert_eq!(3, measure(&config, || 1 + 1).samples().len());
+}
@@ -0,0 +1,72 @@
+// Errors a solution can run into before it gets to solving anything.
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+use crate::parse::ParseError;
+
+/// Why a solution couldn't be run.
+#[derive(Debug)]
+pub enum Error {
+    /// There is no input file where one was expected.
+    MissingInput(PathBuf),
+    /// The input couldn't be read.
+    Io(io::Error),
+    /// The input was read but isn't what the solution expected.
+    Parse(ParseError),
+    /// A parameter given on the command line isn't one the solution takes,
+    /// or has a value it can't use.
+    Param(String),
+    /// The solution panicked, with this message.
+    Panic(String),
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl Error {
+    /// The same error, noting that the input came from `file`.
+    pub fn in_file(self, file: impl fmt::Display) -> Error {
+        match self {
+            Error::Parse(e) => Error::Parse(e.in_file(file)),
+            e => e,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MissingInput(path) => write!(
+                f,
+                "no input at {}; save your puzzle input there or pass --input <path> (- for stdin)",
+                path.display()
+            ),
+            Error::Io(e) => write!(f, "unable to read input: {}", e),
+            Error::Parse(e) => write!(f, "invalid input: {}", e),
+            Error::Param(e) => write!(f, "invalid parameter: {}", e),
+            Error::Panic(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            Error::Parse(e) => Some(e),
+            Error::MissingInput(_) | Error::Param(_) | Error::Panic(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error {
+        Error::Io(e)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Error {
+        Error::Parse(e)
+    }
+}
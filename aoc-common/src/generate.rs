@@ -0,0 +1,135 @@
+// Random puzzle inputs, built to have answers we know in advance.
+//
+// Generators take an `Rng` so that the same seed always gives the same input:
+// a failure found on a generated input can be reproduced from its seed alone.
+use std::ops::RangeInclusive;
+
+use crate::{Answer, Part};
+
+/// A generated puzzle input, along with the answers it was built to have.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generated {
+    pub input: String,
+    pub one: Answer,
+    pub two: Answer,
+}
+
+impl Generated {
+    pub fn new(input: String, one: impl Into<Answer>, two: impl Into<Answer>) -> Generated {
+        Generated {
+            input,
+            one: one.into(),
+            two: two.into(),
+        }
+    }
+
+    pub fn answer(&self, part: Part) -> &Answer {
+        match part {
+            Part::One => &self.one,
+            Part::Two => &self.two,
+        }
+    }
+}
+
+/// Generates an input of roughly `size` lines or records.
+pub type Generator = fn(&mut Rng, usize) -> Generated;
+
+/// A small, seedable pseudo-random number generator (SplitMix64).
+///
+/// Not fit for anything but tests: it's only meant to be fast and to give the
+/// same numbers for the same seed on every platform.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `range`, every one as likely as the others.
+    pub fn range<T: Uniform>(&mut self, range: RangeInclusive<T>) -> T {
+        let (low, high) = (range.start().to_i128(), range.end().to_i128());
+        assert!(low <= high, "empty range");
+        let span = (high - low + 1) as u128;
+        let offset = (self.next_u64() as u128 * span) >> 64;
+        T::from_i128(low + offset as i128)
+    }
+
+    /// An index into a slice of length `len`, which must not be 0.
+    pub fn index(&mut self, len: usize) -> usize {
+        self.range(0..=len - 1)
+    }
+
+    /// A number from 0 up to, but not including, 1.
+    pub fn fraction(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// `true` with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        self.fraction() < p
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.range(0..=i));
+        }
+    }
+}
+
+/// Integers that `Rng::range` can pick from.
+pub trait Uniform: Copy {
+    fn to_i128(self) -> i128;
+    fn from_i128(value: i128) -> Self;
+}
+
+macro_rules! uniform {
+    ($($int:ty),*) => {
+        $(
+            impl Uniform for $int {
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+
+                fn from_i128(value: i128) -> $int {
+                    value as $int
+                }
+            }
+        )*
+    };
+}
+
+uniform!(u8, u32, u64, usize, i32, i64, isize);
+
+#[test]
+fn test_rng() {
+    let numbers = |seed| {
+        let mut rng = Rng::new(seed);
+        (0..100).map(|_| rng.range(-3..=3i64)).collect::<Vec<_>>()
+    };
+    assert_eq!(numbers(7), numbers(7));
+    assert_ne!(numbers(7), numbers(8));
+    for n in -3..=3 {
+        assert!(numbers(7).contains(&n));
+    }
+    assert!(numbers(7).iter().all(|n| (-3..=3).contains(n)));
+
+    let mut rng = Rng::new(0);
+    let mut items: Vec<_> = (0..10).collect();
+    rng.shuffle(&mut items);
+    items.sort();
+    assert_eq!((0..10).collect::<Vec<_>>(), items);
+    assert_eq!(5, rng.range(5..=5u8));
+}
@@ -0,0 +1,360 @@
+// Rectangular grids of squares, like day 3's map of trees.
+//
+// Squares are addressed by `(x, y)`: `x` counts columns from the left and `y`
+// rows from the top, both from 0. The squares are kept row by row in a single
+// `Vec`, so a row is a slice.
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::parse::{self, ParseError};
+
+/// A grid of `T`s, `width` squares wide and `height` high.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    squares: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid of the given `squares`, row by row.
+    ///
+    /// Panics unless there are `width * height` squares.
+    pub fn new(width: usize, height: usize, squares: Vec<T>) -> Grid<T> {
+        assert_eq!(
+            width * height,
+            squares.len(),
+            "a {}x{} grid needs {} squares",
+            width,
+            height,
+            width * height
+        );
+        Grid {
+            width,
+            height,
+            squares,
+        }
+    }
+
+    /// A grid with every square set to `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::new(width, height, vec![value; width * height])
+    }
+
+    /// Parses a grid drawn one row per line, turning each character into a
+    /// square with `square`.
+    ///
+    /// Fails on a character `square` returns `None` for, saying it expected
+    /// `expected`, or on a row that isn't as wide as the first.
+    pub fn parse<F>(input: &str, expected: &str, mut square: F) -> Result<Grid<T>, ParseError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut width = None;
+        let rows: Vec<Vec<T>> = parse::lines(input, |line| {
+            let row = line
+                .char_indices()
+                .map(|(i, c)| {
+                    square(c)
+                        .ok_or_else(|| ParseError::at(line, &line[i..i + c.len_utf8()], expected))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let width = *width.get_or_insert(row.len());
+            match line.char_indices().nth(width) {
+                Some((i, _)) => Err(ParseError::at(line, &line[i..], "the end of the row")),
+                None if row.len() < width => Err(ParseError::missing(
+                    line,
+                    &format!("a row {} squares wide", width),
+                )),
+                None => Ok(row),
+            }
+        })?;
+
+        let height = rows.len();
+        Ok(Grid::new(
+            width.unwrap_or(0),
+            height,
+            rows.into_iter().flatten().collect(),
+        ))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The square at `(x, y)`, if it's on the grid.
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x < self.width && y < self.height {
+            Some(&self.squares[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            Some(&mut self.squares[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// The square at `(x, y)` of the grid repeated forever in every
+    /// direction, so that `x` and `y` may be anywhere, negative included.
+    ///
+    /// Panics if the grid is empty.
+    pub fn get_wrapping(&self, x: isize, y: isize) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        &self[(x, y)]
+    }
+
+    /// The squares beside `(x, y)` above, below, left and right that are on
+    /// the grid, in reading order.
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbours(x, y, &[(0, -1), (-1, 0), (1, 0), (0, 1)])
+    }
+
+    /// The squares around `(x, y)` that are on the grid, diagonals included,
+    /// in reading order.
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbours(
+            x,
+            y,
+            &[
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (-1, 0),
+                (1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+            ],
+        )
+    }
+
+    fn neighbours(
+        &self,
+        x: usize,
+        y: usize,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let x = x.checked_add_signed(dx)?;
+            let y = y.checked_add_signed(dy)?;
+            self.get(x, y).map(|_| (x, y))
+        })
+    }
+
+    /// Row `y`, from left to right.
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.squares[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Every row, from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).map(move |y| self.row(y))
+    }
+
+    /// Column `x`, from top to bottom.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(x < self.width, "column {} of {}", x, self.width);
+        self.squares.iter().skip(x).step_by(self.width)
+    }
+
+    /// Every column, from left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// Every square with where it is, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        let width = self.width;
+        self.squares
+            .iter()
+            .enumerate()
+            .map(move |(i, square)| ((i % width, i / width), square))
+    }
+
+    /// The grid with `f` applied to every square.
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid::new(
+            self.width,
+            self.height,
+            self.squares.iter().map(f).collect(),
+        )
+    }
+
+    /// The grid turned a quarter turn clockwise.
+    pub fn rotate_clockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        // The new rows are the old columns, read from the bottom up.
+        self.transform(self.height, self.width, |x, y| (y, self.height - 1 - x))
+    }
+
+    /// The grid turned a quarter turn anticlockwise.
+    pub fn rotate_anticlockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.transform(self.height, self.width, |x, y| (self.width - 1 - y, x))
+    }
+
+    /// The grid mirrored left to right.
+    pub fn flip_horizontal(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.transform(self.width, self.height, |x, y| (self.width - 1 - x, y))
+    }
+
+    /// The grid mirrored top to bottom.
+    pub fn flip_vertical(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.transform(self.width, self.height, |x, y| (x, self.height - 1 - y))
+    }
+
+    /// A `width` by `height` grid whose square at `(x, y)` is this grid's
+    /// square at `from(x, y)`.
+    fn transform<F>(&self, width: usize, height: usize, from: F) -> Grid<T>
+    where
+        T: Clone,
+        F: Fn(usize, usize) -> (usize, usize),
+    {
+        let squares = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| self[from(x, y)].clone())
+            .collect();
+        Grid::new(width, height, squares)
+    }
+
+    /// The grid drawn one row per line, with `square` choosing each
+    /// square's character.
+    pub fn display<'a, F>(&'a self, square: F) -> impl fmt::Display + 'a
+    where
+        F: Fn(&T) -> char + 'a,
+    {
+        Drawing { grid: self, square }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    /// The square at `(x, y)`. Panics if it's off the grid.
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y).unwrap_or_else(|| {
+            panic!(
+                "({}, {}) is off a {}x{} grid",
+                x, y, self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(x, y)
+            .unwrap_or_else(|| panic!("({}, {}) is off a {}x{} grid", x, y, width, height))
+    }
+}
+
+/// A grid of characters is drawn as it was parsed.
+impl fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.display(|&c| c))
+    }
+}
+
+struct Drawing<'a, T, F> {
+    grid: &'a Grid<T>,
+    square: F,
+}
+
+impl<T, F: Fn(&T) -> char> fmt::Display for Drawing<'_, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.grid.rows() {
+            let row: String = row.iter().map(&self.square).collect();
+            writeln!(f, "{}", row)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_grid() {
+    let tree = |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    };
+    let grid = Grid::parse("..#\n#..\n", "`.` or `#`", tree).unwrap();
+    assert_eq!((3, 2), (grid.width(), grid.height()));
+    assert_eq!(Some(&true), grid.get(2, 0));
+    assert_eq!(None, grid.get(3, 0));
+    assert!(grid[(0, 1)]);
+    assert!(*grid.get_wrapping(5, 2));
+    assert!(!*grid.get_wrapping(-1, -1));
+    assert_eq!(&[true, false, false], grid.row(1));
+    let columns: Vec<Vec<_>> = grid.columns().map(|c| c.copied().collect()).collect();
+    assert_eq!(
+        vec![vec![false, true], vec![false, false], vec![true, false]],
+        columns
+    );
+    assert_eq!(
+        vec![((2, 0), &true), ((0, 1), &true)],
+        grid.iter().filter(|(_, &tree)| tree).collect::<Vec<_>>()
+    );
+
+    let draw = |grid: &Grid<bool>| {
+        grid.display(|&tree| if tree { '#' } else { '.' })
+            .to_string()
+    };
+    assert_eq!("..#\n#..\n", draw(&grid));
+    assert_eq!("#.\n..\n.#\n", draw(&grid.rotate_clockwise()));
+    assert_eq!(
+        "#.\n..\n.#\n",
+        draw(
+            &grid
+                .rotate_anticlockwise()
+                .flip_horizontal()
+                .flip_vertical()
+        )
+    );
+    assert_eq!(grid, grid.rotate_clockwise().rotate_anticlockwise());
+    assert_eq!("#..\n..#\n", draw(&grid.flip_horizontal()));
+    assert_eq!("#..\n..#\n", draw(&grid.flip_vertical()));
+    assert_eq!(
+        "ab\ncd\n",
+        Grid::new(2, 2, vec!['a', 'b', 'c', 'd']).to_string()
+    );
+
+    let neighbours: Vec<_> = grid.neighbours4(0, 0).collect();
+    assert_eq!(vec![(1, 0), (0, 1)], neighbours);
+    let neighbours: Vec<_> = grid.neighbours8(1, 1).collect();
+    assert_eq!(vec![(0, 0), (1, 0), (2, 0), (0, 1), (2, 1)], neighbours);
+
+    let e = Grid::parse("..#\n.o.\n", "`.` or `#`", tree).unwrap_err();
+    assert_eq!((2, 2, "o"), (e.line, e.column, &*e.found));
+    let e = Grid::parse("..#\n....\n", "`.` or `#`", tree).unwrap_err();
+    assert_eq!((2, 4, "."), (e.line, e.column, &*e.found));
+    let e = Grid::parse("..#\n..\n", "`.` or `#`", tree).unwrap_err();
+    assert_eq!(
+        (2, 3, "a row 3 squares wide"),
+        (e.line, e.column, &*e.expected)
+    );
+    assert_eq!(0, Grid::parse("", "", tree).unwrap().height());
+}
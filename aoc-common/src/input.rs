@@ -0,0 +1,191 @@
+// Finding and reading puzzle inputs.
+//
+// Every author keeps their inputs under `inputs/<author>/day-<day>.txt` at the
+// root of the workspace. Binaries and the runner accept `--input <path>` to
+// use another file instead, or `--input -` to read standard input,
+// `--format json` to print results for scripts instead of for people, and
+// `--param name=value` to change one of the puzzle's constants.
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process;
+
+use crate::params::Params;
+use crate::report::{Format, Report};
+use crate::{Error, Part, Result, Solution};
+
+/// Where a solution's input is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    /// The source named by an `--input` argument; `-` means standard input.
+    pub fn from_arg(arg: &str) -> Source {
+        match arg {
+            "-" => Source::Stdin,
+            path => Source::File(PathBuf::from(path)),
+        }
+    }
+
+    /// Where `author` keeps their input for `day`.
+    pub fn standard(day: u8, author: &str) -> Source {
+        Source::File(inputs_dir().join(author).join(format!("day-{}.txt", day)))
+    }
+
+    /// The source named by `arg` if there is one, otherwise the standard one.
+    pub fn locate(day: u8, author: &str, arg: Option<&str>) -> Source {
+        arg.map_or_else(|| Source::standard(day, author), Source::from_arg)
+    }
+
+    pub fn read(&self) -> Result<String> {
+        match self {
+            Source::File(path) => fs::read_to_string(path).map_err(|e| match e.kind() {
+                io::ErrorKind::NotFound => Error::MissingInput(path.clone()),
+                _ => Error::Io(e),
+            }),
+            Source::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::Stdin => f.write_str("<stdin>"),
+        }
+    }
+}
+
+/// The `inputs` folder at the root of the workspace, unless overridden by the
+/// `AOC_INPUTS` environment variable.
+pub fn inputs_dir() -> PathBuf {
+    env::var_os("AOC_INPUTS")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            let common = Path::new(env!("CARGO_MANIFEST_DIR"));
+            common.parent().unwrap_or(common).join("inputs")
+        })
+}
+
+/// Entry point for an author's binary.
+///
+/// Reads the input named on the command line with `--input`, or the author's
+/// standard input for the solution's day, and hands it to `main`; or with
+/// `--format json`, prints a [`Report`] of each part of `solution` instead.
+/// Given any `--param`, prints each part's answer from `solution` rather than
+/// going through `main`, which only knows the puzzle's own constants.
+/// Exits with a message instead of panicking when the input can't be read or
+/// parsed.
+pub fn run_with_input(solution: Solution, main: fn(&str) -> Result<()>) {
+    let (day, author) = (solution.day, solution.author);
+    let args = match Args::parse(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!(
+                "{}\nusage: day-{}-{} [--input <path>|-] [--format text|json] [--param <name>=<value>]...",
+                e, day, author
+            );
+            process::exit(2);
+        }
+    };
+    let source = Source::locate(day, author, args.input.as_deref());
+    let result = source.read().and_then(|input| {
+        if args.format == Format::Text && args.params.is_empty() {
+            return main(&input);
+        }
+        for report in Report::solve(&solution, &input, &args.params, &Part::ALL)? {
+            match args.format {
+                Format::Text => println!("Part {}: {}", report.part, report.answer),
+                Format::Json => println!("{}", report.to_json()),
+            }
+        }
+        Ok(())
+    });
+    if let Err(e) = result {
+        eprintln!("day-{}-{}: {}", day, author, e.in_file(&source));
+        process::exit(1);
+    }
+}
+
+/// What a binary was asked for on the command line.
+#[derive(Debug, PartialEq, Eq)]
+struct Args {
+    input: Option<String>,
+    format: Format,
+    params: Params,
+}
+
+impl Args {
+    fn parse<I: Iterator<Item = String>>(mut args: I) -> Result<Args, String> {
+        let mut parsed = Args {
+            input: None,
+            format: Format::Text,
+            params: Params::default(),
+        };
+        let mut params = vec![];
+        while let Some(arg) = args.next() {
+            let (flag, value) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let value = |what: &str| {
+                value
+                    .or_else(|| args.next())
+                    .ok_or(format!("{} needs {}", flag, what))
+            };
+            match flag {
+                "-i" | "--input" => parsed.input = Some(value("a path")?),
+                "-f" | "--format" => parsed.format = value("a format")?.parse()?,
+                "--param" => params.push(value("a name=value")?),
+                _ => return Err(format!("unexpected argument `{}`", arg)),
+            }
+        }
+        parsed.params = Params::from_args(&params)?;
+        Ok(parsed)
+    }
+}
+
+#[test]
+fn test_locate() {
+    let args = |args: &[&str]| Args::parse(args.iter().map(|arg| arg.to_string()));
+    let input = |arg_list: &[&str]| args(arg_list).map(|args| args.input);
+    assert_eq!(Ok(None), input(&[]));
+    assert_eq!(Ok(Some("-".to_string())), input(&["--input", "-"]));
+    assert_eq!(Ok(Some("a.txt".to_string())), input(&["--input=a.txt"]));
+    assert!(args(&["--input"]).is_err());
+    assert_eq!(
+        Ok(Args {
+            input: Some("a.txt".to_string()),
+            format: Format::Json,
+            params: Params::default(),
+        }),
+        args(&["--format", "json", "-i", "a.txt"])
+    );
+    assert_eq!(
+        Ok(Params::from_args(&["target=42", "bag=dim red"]).unwrap()),
+        args(&["--param", "target=42", "--param=bag=dim red"]).map(|args| args.params)
+    );
+    assert!(args(&["--param", "target"]).is_err());
+    assert!(args(&["--format=xml"]).is_err());
+    assert!(args(&["--verbose"]).is_err());
+
+    assert_eq!(Source::Stdin, Source::locate(4, "matt", Some("-")));
+    assert_eq!(
+        Source::File(inputs_dir().join("matt").join("day-4.txt")),
+        Source::locate(4, "matt", None)
+    );
+    match Source::locate(4, "nobody", None).read() {
+        Err(Error::MissingInput(path)) => assert!(path.ends_with("nobody/day-4.txt")),
+        _ => panic!("expected a missing input"),
+    }
+}
@@ -0,0 +1,157 @@
+// Just enough JSON to print results for scripts to read, one object per line.
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::allocations::Allocations;
+use crate::Answer;
+
+/// A JSON value. Objects keep their keys in the order they were added.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Str(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// An object with the given keys and values, in order.
+    pub fn object<K: Into<String>>(fields: Vec<(K, Json)>) -> Json {
+        Json::Object(fields.into_iter().map(|(k, v)| (k.into(), v)).collect())
+    }
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Json::Null => f.write_str("null"),
+            Json::Bool(value) => write!(f, "{}", value),
+            Json::Int(value) => write!(f, "{}", value),
+            Json::Str(value) => write_str(f, value),
+            Json::Array(values) => {
+                f.write_str("[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                f.write_str("]")
+            }
+            Json::Object(fields) => {
+                f.write_str("{")?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write_str(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+fn write_str(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_str("\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    f.write_str("\"")
+}
+
+impl From<bool> for Json {
+    fn from(value: bool) -> Json {
+        Json::Bool(value)
+    }
+}
+
+// Integers too big for an `i64` are written as strings rather than wrapped.
+macro_rules! json_from_int {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for Json {
+                fn from(value: $int) -> Json {
+                    i64::try_from(value).map_or_else(|_| Json::Str(value.to_string()), Json::Int)
+                }
+            }
+        )*
+    };
+}
+
+json_from_int!(u8, i32, u32, isize, usize, u64);
+
+impl From<i64> for Json {
+    fn from(value: i64) -> Json {
+        Json::Int(value)
+    }
+}
+
+impl From<&str> for Json {
+    fn from(value: &str) -> Json {
+        Json::Str(value.to_string())
+    }
+}
+
+impl From<String> for Json {
+    fn from(value: String) -> Json {
+        Json::Str(value)
+    }
+}
+
+impl<T: Into<Json>> From<Vec<T>> for Json {
+    fn from(values: Vec<T>) -> Json {
+        Json::Array(values.into_iter().map(Into::into).collect())
+    }
+}
+
+impl From<Allocations> for Json {
+    fn from(allocations: Allocations) -> Json {
+        Json::object(vec![
+            ("count", allocations.count.into()),
+            ("bytes", allocations.bytes.into()),
+            ("peak", allocations.peak.into()),
+        ])
+    }
+}
+
+/// Numbers stay numbers, and an unsolved part is `null`.
+impl From<&Answer> for Json {
+    fn from(answer: &Answer) -> Json {
+        match answer {
+            Answer::Int(value) => Json::Int(*value),
+            Answer::Text(value) => Json::Str(value.clone()),
+            Answer::Unsolved => Json::Null,
+        }
+    }
+}
+
+#[test]
+fn test_json() {
+    let json = Json::object(vec![
+        ("day", 1.into()),
+        ("answer", (&Answer::Unsolved).into()),
+        ("entries", vec![1721, 299].into()),
+        ("text", "say \"hi\"\n\u{1}".into()),
+        ("looped", true.into()),
+        ("empty", Json::object::<&str>(vec![])),
+    ]);
+    assert_eq!(
+        r#"{"day":1,"answer":null,"entries":[1721,299],"text":"say \"hi\"\n\u0001","looped":true,"empty":{}}"#,
+        json.to_string()
+    );
+
+    assert_eq!(Json::Int(i64::MAX), (i64::MAX as u64).into());
+    assert_eq!(Json::Str(u64::MAX.to_string()), u64::MAX.into());
+}
@@ -0,0 +1,27 @@
+//! Helpers shared by every day's solutions.
+//!
+//! Each `day-x` crate depends on this one so that authors don't have to copy
+//! the same input readers and timing code into every binary, and so that all
+//! solutions can be driven through the same [`Solver`] trait.
+pub mod allocations;
+mod answer;
+pub mod bench;
+mod error;
+pub mod generate;
+pub mod grid;
+pub mod input;
+pub mod json;
+pub mod panics;
+pub mod params;
+pub mod parse;
+mod part;
+pub mod records;
+pub mod report;
+mod solver;
+pub mod timing;
+
+pub use answer::Answer;
+pub use error::{Error, Result};
+pub use parse::ParseError;
+pub use part::Part;
+pub use solver::{Parsed, Solution, Solver};
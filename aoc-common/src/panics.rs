@@ -0,0 +1,46 @@
+// Keeping one solution's panic from taking down every other solution being
+// run alongside it, by turning the panic into an error for that solution.
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Mutex, PoisonError};
+
+/// Runs `f` without printing the message of any panic caught inside it.
+///
+/// The panic hook is global, so only one thread at a time may swap it out.
+pub fn silenced<T, F: FnOnce() -> T>(f: F) -> T {
+    static SWAPPING: Mutex<()> = Mutex::new(());
+    let _swapping = SWAPPING.lock().unwrap_or_else(PoisonError::into_inner);
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = f();
+    panic::set_hook(hook);
+    result
+}
+
+/// Runs `f`, turning a panic into its message.
+pub fn catch<T, F: FnOnce() -> T>(f: F) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| panic_message(&*payload))
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        format!("panicked: {}", message)
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        format!("panicked: {}", message)
+    } else {
+        "panicked".to_string()
+    }
+}
+
+#[test]
+fn test_catch() {
+    assert_eq!(Ok(1), silenced(|| catch(|| 1)));
+    assert_eq!(
+        Err("panicked: no answer".to_string()),
+        silenced(|| catch(|| -> u8 { panic!("no answer") }))
+    );
+    assert_eq!(
+        Err("panicked: 2 > 1".to_string()),
+        silenced(|| catch(|| -> u8 { panic!("{} > {}", 2, 1) }))
+    );
+}
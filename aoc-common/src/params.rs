@@ -0,0 +1,163 @@
+// Puzzle constants, like day 1's 2020, that the command line can change with
+// `--param name=value`, to explore variants of a puzzle without editing it.
+//
+// A solver declares the constants it takes in `Solver::PARAMETERS`, with the
+// puzzle's own values as defaults, and reads them in `Solver::parse_with`,
+// keeping what the parts need in its parsed input.
+use std::str::FromStr;
+
+use crate::{Error, Result};
+
+/// A puzzle constant that a solver lets the command line change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parameter {
+    pub name: &'static str,
+    /// The puzzle's value, written as it would be on the command line.
+    pub default: &'static str,
+    pub about: &'static str,
+}
+
+/// Values for parameters, by name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params(Vec<(String, String)>);
+
+impl Params {
+    /// Every one of `parameters` set to its default.
+    pub fn defaults(parameters: &[Parameter]) -> Params {
+        Params(
+            parameters
+                .iter()
+                .map(|p| (p.name.to_string(), p.default.to_string()))
+                .collect(),
+        )
+    }
+
+    /// The values given as `name=value` arguments. A later value for the
+    /// same name replaces an earlier one.
+    pub fn from_args<S: AsRef<str>>(args: &[S]) -> Result<Params, String> {
+        let mut params = Params::default();
+        for arg in args {
+            let arg = arg.as_ref();
+            let (name, value) = arg
+                .split_once('=')
+                .ok_or_else(|| format!("expected a parameter as `name=value`, got `{}`", arg))?;
+            params.0.retain(|(n, _)| n != name);
+            params.0.push((name.to_string(), value.to_string()));
+        }
+        Ok(params)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Every one of `parameters` set to the value given for it, or else to
+    /// its default. Fails if a value is given for anything else.
+    pub fn resolve(&self, parameters: &[Parameter]) -> Result<Params> {
+        if let Some((name, _)) = self
+            .0
+            .iter()
+            .find(|(name, _)| !parameters.iter().any(|p| p.name == name))
+        {
+            let known: Vec<_> = parameters.iter().map(|p| p.name).collect();
+            return Err(Error::Param(match known.len() {
+                0 => format!("there's no `{}`; this solution takes no parameters", name),
+                _ => format!(
+                    "there's no `{}`; expected one of {}",
+                    name,
+                    known.join(", ")
+                ),
+            }));
+        }
+
+        let mut resolved = Params::defaults(parameters);
+        for (name, value) in &mut resolved.0 {
+            if let Some((_, given)) = self.0.iter().find(|(n, _)| n == name) {
+                *value = given.clone();
+            }
+        }
+        Ok(resolved)
+    }
+
+    /// The value of `name`, parsed as a `T`, which is described as
+    /// `expected` if it doesn't parse.
+    pub fn get<T: FromStr>(&self, name: &str, expected: &str) -> Result<T> {
+        self.get_with(name, expected, |value| value.parse().ok())
+    }
+
+    /// The value of `name`, parsed with `parse`.
+    ///
+    /// Panics if `name` has no value, which `resolve` makes sure of for every
+    /// parameter a solver declares.
+    pub fn get_with<T, F>(&self, name: &str, expected: &str, parse: F) -> Result<T>
+    where
+        F: FnOnce(&str) -> Option<T>,
+    {
+        let value = self
+            .0
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value)
+            .unwrap_or_else(|| panic!("no parameter `{}`; is it declared?", name));
+        parse(value)
+            .ok_or_else(|| Error::Param(format!("`{}={}`, expected {}", name, value, expected)))
+    }
+}
+
+/// Parses a list of `T`s separated by commas, e.g. `1:1,3:1`.
+pub fn list<T, F: Fn(&str) -> Option<T>>(value: &str, item: F) -> Option<Vec<T>> {
+    value.split(',').map(|s| item(s.trim())).collect()
+}
+
+#[test]
+fn test_params() {
+    const PARAMETERS: &[Parameter] = &[
+        Parameter {
+            name: "target",
+            default: "2020",
+            about: "what the entries sum to",
+        },
+        Parameter {
+            name: "bag",
+            default: "shiny gold",
+            about: "the bag to look for",
+        },
+    ];
+
+    let params = Params::defaults(PARAMETERS);
+    assert_eq!(2020, params.get::<u32>("target", "a number").unwrap());
+
+    let given = Params::from_args(&["target=42", "target=7"]).unwrap();
+    let params = given.resolve(PARAMETERS).unwrap();
+    assert_eq!(7, params.get::<u32>("target", "a number").unwrap());
+    assert_eq!("shiny gold", params.get::<String>("bag", "a bag").unwrap());
+
+    let e = Params::from_args(&["target=x"])
+        .unwrap()
+        .resolve(PARAMETERS)
+        .unwrap()
+        .get::<u32>("target", "a number")
+        .unwrap_err();
+    assert_eq!(
+        "invalid parameter: `target=x`, expected a number",
+        e.to_string()
+    );
+    let e = Params::from_args(&["slope=3:1"])
+        .unwrap()
+        .resolve(PARAMETERS)
+        .unwrap_err();
+    assert_eq!(
+        "invalid parameter: there's no `slope`; expected one of target, bag",
+        e.to_string()
+    );
+    assert!(given.resolve(&[]).is_err());
+    assert!(Params::from_args(&["target"]).is_err());
+    assert!(Params::default().resolve(&[]).unwrap().is_empty());
+
+    let pair = |s: &str| {
+        s.split_once(':')
+            .and_then(|(a, b)| Some((a.parse().ok()?, b.parse().ok()?)))
+    };
+    assert_eq!(Some(vec![(1u8, 1u8), (3, 1)]), list("1:1, 3:1", pair));
+    assert_eq!(None, list("1:1,3", pair));
+}
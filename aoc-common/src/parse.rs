@@ -0,0 +1,161 @@
+// Parsing helpers that report where bad input is instead of panicking.
+//
+// Errors point at a `span`: a slice of the text being parsed. Parsers that
+// work on one line at a time build errors against that line and let
+// `lines` fix up the line number, so they never need the whole input.
+use std::fmt;
+use std::iter::{self, FromIterator};
+use std::str::FromStr;
+
+/// Input that isn't what a parser expected, and where it was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The file the input came from, once known.
+    pub file: Option<String>,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, in characters.
+    pub column: usize,
+    /// What the parser expected to find.
+    pub expected: String,
+    /// What it found instead; empty at the end of a line.
+    pub found: String,
+    /// The whole offending line.
+    pub text: String,
+}
+
+impl ParseError {
+    /// An error at `span`, which must be a slice of `text`.
+    ///
+    /// A span that isn't part of `text` is reported at its end.
+    pub fn at(text: &str, span: &str, expected: &str) -> ParseError {
+        let start = text.as_ptr() as usize;
+        let offset = (span.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|offset| offset + span.len() <= text.len())
+            .unwrap_or(text.len());
+        let line_start = text[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = text[offset..].find('\n').map_or(text.len(), |i| offset + i);
+        let line = &text[line_start..line_end];
+
+        ParseError {
+            file: None,
+            line: text[..line_start].matches('\n').count() + 1,
+            column: text[line_start..offset].chars().count() + 1,
+            expected: expected.to_string(),
+            found: text[offset..(offset + span.len()).min(line_end)].to_string(),
+            text: line.trim_end_matches('\r').to_string(),
+        }
+    }
+
+    /// An error for something missing from the end of `text`.
+    pub fn missing(text: &str, expected: &str) -> ParseError {
+        ParseError::at(text, &text[text.len()..], expected)
+    }
+
+    /// The same error, for text that started `lines` lines further down.
+    pub fn offset_lines(mut self, lines: usize) -> ParseError {
+        self.line += lines;
+        self
+    }
+
+    /// The same error, for input read from `file`.
+    pub fn in_file(mut self, file: impl fmt::Display) -> ParseError {
+        self.file = Some(file.to_string());
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.file {
+            Some(file) => write!(f, "{}:{}:{}: ", file, self.line, self.column)?,
+            None => write!(f, "line {}, column {}: ", self.line, self.column)?,
+        }
+        write!(f, "expected {}, found ", self.expected)?;
+        if self.found.is_empty() {
+            f.write_str("end of line")?;
+        } else {
+            write!(f, "`{}`", self.found)?;
+        }
+
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        writeln!(f)?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", number, self.text)?;
+        // Keep tabs so that the underline lines up with the text above it.
+        let indent: String = self
+            .text
+            .chars()
+            .chain(iter::repeat(' '))
+            .take(self.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        write!(
+            f,
+            "{} | {}{}",
+            gutter,
+            indent,
+            "^".repeat(self.found.chars().count().max(1))
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses every line of `input` with `parse`, numbering any error it returns.
+pub fn lines<T, C, F>(input: &str, mut parse: F) -> Result<C, ParseError>
+where
+    F: FnMut(&str) -> Result<T, ParseError>,
+    C: FromIterator<T>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse(line).map_err(|e| e.offset_lines(i)))
+        .collect()
+}
+
+/// Parses `span`, a slice of `text`, as a `T`.
+pub fn value<T: FromStr>(text: &str, span: &str, expected: &str) -> Result<T, ParseError> {
+    span.parse()
+        .map_err(|_| ParseError::at(text, span, expected))
+}
+
+/// Splits `span`, a slice of `text`, around the first `delimiter`.
+pub fn split_once<'a>(
+    text: &str,
+    span: &'a str,
+    delimiter: &str,
+    expected: &str,
+) -> Result<(&'a str, &'a str), ParseError> {
+    match span.find(delimiter) {
+        Some(i) => Ok((&span[..i], &span[i + delimiter.len()..])),
+        None => Err(ParseError::at(text, span, expected)),
+    }
+}
+
+#[test]
+fn test_parse_error() {
+    let input = "nop +0\nacc x1\n";
+    let line = input.lines().nth(1).unwrap();
+    let e = ParseError::at(input, &line[4..5], "`+` or `-`");
+    assert_eq!((2, 5), (e.line, e.column));
+    assert_eq!("x", e.found);
+    assert_eq!(
+        "day-8.txt:2:5: expected `+` or `-`, found `x`\n  |\n2 | acc x1\n  |     ^",
+        e.in_file("day-8.txt").to_string()
+    );
+}
+
+#[test]
+fn test_lines() {
+    let parsed: Result<Vec<u32>, _> = lines("1\n2\nthree\n", |line| value(line, line, "a number"));
+    let e = parsed.unwrap_err();
+    assert_eq!((3, 1, "three"), (e.line, e.column, e.found.as_str()));
+
+    let e = ParseError::missing("acc", "an argument");
+    assert_eq!((4, ""), (e.column, e.found.as_str()));
+    assert!(e.to_string().contains("found end of line"));
+}
@@ -0,0 +1,43 @@
+// The two halves of every puzzle.
+use std::fmt;
+use std::str::FromStr;
+
+/// One of the two parts of a day's puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    /// Both parts, in the order they are solved.
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => f.write_str("One"),
+            Part::Two => f.write_str("Two"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Part, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "1" | "one" => Ok(Part::One),
+            "2" | "two" => Ok(Part::Two),
+            _ => Err(format!("invalid part `{}`, expected 1 or 2", s)),
+        }
+    }
+}
+
+#[test]
+fn test_parse_part() {
+    assert_eq!(Ok(Part::One), "1".parse());
+    assert_eq!(Ok(Part::Two), "Two".parse());
+    assert!("3".parse::<Part>().is_err());
+}
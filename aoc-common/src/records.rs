@@ -0,0 +1,122 @@
+// Records separated by blank lines, like day 4's passports and day 6's groups.
+//
+// Records are read lazily, one line at a time, so a whole file never needs to
+// be held in memory. Lines may end in `\n` or `\r\n`. Any run of blank lines,
+// including lines of nothing but whitespace, separates two records; blank
+// lines at the start or the end of the input don't make empty records.
+use std::io::{self, BufRead};
+
+use crate::ParseError;
+
+/// One record: a run of lines that aren't blank.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    /// The 1-based line number of the record's first line.
+    pub line: usize,
+    /// The record's lines joined by `\n`, without their line endings but
+    /// otherwise as they were, leading whitespace included.
+    pub text: String,
+}
+
+impl Record {
+    pub fn lines(&self) -> std::str::Lines<'_> {
+        self.text.lines()
+    }
+
+    /// Parses the record's text with `parse`, numbering any error it returns
+    /// by its line in the whole input.
+    pub fn parse<T, F>(&self, parse: F) -> Result<T, ParseError>
+    where
+        F: FnOnce(&str) -> Result<T, ParseError>,
+    {
+        parse(&self.text).map_err(|e| e.offset_lines(self.line - 1))
+    }
+}
+
+/// An iterator over the records read from a [`BufRead`].
+#[derive(Debug)]
+pub struct Records<R> {
+    reader: R,
+    /// How many lines have been read so far.
+    line: usize,
+    buffer: String,
+}
+
+/// The records in `reader`, read as they're asked for. Solvers given the
+/// whole input read it with `records(input.as_bytes())`.
+pub fn records<R: BufRead>(reader: R) -> Records<R> {
+    Records {
+        reader,
+        line: 0,
+        buffer: String::new(),
+    }
+}
+
+impl<R: BufRead> Iterator for Records<R> {
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut record: Option<Record> = None;
+        loop {
+            self.buffer.clear();
+            match self.reader.read_line(&mut self.buffer) {
+                Ok(0) => return record.map(Ok),
+                Ok(_) => self.line += 1,
+                Err(e) => return Some(Err(e)),
+            }
+
+            let line = self.buffer.trim_end_matches(&['\n', '\r'][..]);
+            if line.trim().is_empty() {
+                if record.is_some() {
+                    return record.map(Ok);
+                }
+            } else if let Some(record) = &mut record {
+                record.text.push('\n');
+                record.text.push_str(line);
+            } else {
+                record = Some(Record {
+                    line: self.line,
+                    text: line.to_string(),
+                });
+            }
+        }
+    }
+}
+
+#[test]
+fn test_records() {
+    let read = |input: &str| -> Vec<(usize, String)> {
+        records(input.as_bytes())
+            .map(|record| record.map(|record| (record.line, record.text)).unwrap())
+            .collect()
+    };
+    let record = |line, text: &str| (line, text.to_string());
+
+    assert_eq!(vec![record(1, "a\nb"), record(4, "c")], read("a\nb\n\nc\n"));
+    assert_eq!(
+        vec![record(1, "a\nb"), record(4, "c")],
+        read("a\r\nb\r\n\r\nc")
+    );
+    assert_eq!(vec![record(1, "a")], read("a\n\n\n"));
+    assert_eq!(
+        vec![record(3, "  a\n\tb"), record(6, "c")],
+        read("\n \n  a\n\tb\n\t\nc\n\n")
+    );
+    assert!(read("").is_empty());
+    assert!(read("\n\r\n  \n").is_empty());
+
+    let invalid = [b'a', b'\n', 0xff, b'\n'];
+    assert_eq!(
+        io::ErrorKind::InvalidData,
+        records(&invalid[..]).next().unwrap().unwrap_err().kind()
+    );
+
+    let second = records("a\n\nbc\nd!\n".as_bytes()).nth(1).unwrap().unwrap();
+    let e = second
+        .parse(|text| -> Result<(), _> {
+            let line = text.lines().nth(1).unwrap();
+            Err(ParseError::at(text, &line[1..], "a letter"))
+        })
+        .unwrap_err();
+    assert_eq!((4, 2, "!"), (e.line, e.column, &*e.found));
+}
@@ -0,0 +1,208 @@
+// Results for scripts to read: with `--format json`, every solution prints
+// one JSON object per part instead of its author's own output.
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::allocations::{counted, Allocations};
+use crate::json::Json;
+use crate::panics::{catch, silenced};
+use crate::params::Params;
+use crate::timing::timed;
+use crate::{Answer, Error, Part, Result, Solution};
+
+/// How a solution prints its results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// However its author likes.
+    Text,
+    /// One [`Report`] per part, as a line of JSON.
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("expected `text` or `json`, got `{}`", s)),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Format::Text => f.write_str("text"),
+            Format::Json => f.write_str("json"),
+        }
+    }
+}
+
+/// One part solved by one solution, with how long it took.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub day: u8,
+    pub author: &'static str,
+    pub variant: Option<&'static str>,
+    pub part: Part,
+    pub answer: Answer,
+    /// Values besides the answer that the solution reports, like the entries
+    /// that make it up.
+    pub auxiliary: Vec<(&'static str, Json)>,
+    /// How long parsing the input took, shared by both parts.
+    pub parse: Duration,
+    pub solve: Duration,
+    /// What parsing allocated, if allocations are being counted.
+    pub parse_allocations: Option<Allocations>,
+    pub solve_allocations: Option<Allocations>,
+}
+
+impl Report {
+    /// Parses `input` once, with `params` changed from their defaults, and
+    /// solves each of `parts` from it.
+    pub fn solve(
+        solution: &Solution,
+        input: &str,
+        params: &Params,
+        parts: &[Part],
+    ) -> Result<Vec<Report>> {
+        // A panic is an error for this solution alone, like unparseable input.
+        silenced(|| {
+            let ((parsed, parse_allocations), parse) =
+                timed(|| counted(|| catch(|| solution.parse_with(input, params))));
+            let parsed = parsed.map_err(Error::Panic)??;
+            parts
+                .iter()
+                .map(|&part| {
+                    let ((answer, solve_allocations), solve) =
+                        timed(|| counted(|| catch(|| solution.solve(&parsed, part))));
+                    Ok(Report {
+                        day: solution.day,
+                        author: solution.author,
+                        variant: solution.variant,
+                        part,
+                        answer: answer.map_err(Error::Panic)?,
+                        auxiliary: catch(|| solution.auxiliary(&parsed, part))
+                            .map_err(Error::Panic)?,
+                        parse,
+                        solve,
+                        parse_allocations,
+                        solve_allocations,
+                    })
+                })
+                .collect()
+        })
+    }
+
+    /// The report as an object: `day`, `author`, `variant` if there is one,
+    /// `part`, `answer` (`null` if unsolved), `auxiliary`, `parse_ns` and
+    /// `solve_ns`, and if allocations were counted, `parse_allocations` and
+    /// `solve_allocations`, each with a `count`, `bytes` and `peak`.
+    pub fn to_json(&self) -> Json {
+        let mut fields = vec![("day", self.day.into()), ("author", self.author.into())];
+        if let Some(variant) = self.variant {
+            fields.push(("variant", variant.into()));
+        }
+        fields.extend(vec![
+            ("part", self.part.to_string().to_lowercase().into()),
+            ("answer", (&self.answer).into()),
+            ("auxiliary", Json::object(self.auxiliary.clone())),
+            ("parse_ns", (self.parse.as_nanos() as u64).into()),
+            ("solve_ns", (self.solve.as_nanos() as u64).into()),
+        ]);
+        let allocations = [
+            ("parse_allocations", self.parse_allocations),
+            ("solve_allocations", self.solve_allocations),
+        ];
+        for (key, allocations) in allocations {
+            if let Some(allocations) = allocations {
+                fields.push((key, allocations.into()));
+            }
+        }
+        Json::object(fields)
+    }
+}
+
+#[test]
+fn test_report() {
+    use crate::Solver;
+
+    struct Sum;
+
+    impl Solver for Sum {
+        type Input = Vec<i64>;
+
+        fn parse(input: &str) -> Result<Self::Input> {
+            Ok(crate::parse::lines(input, |line| {
+                crate::parse::value(line, line, "a number")
+            })?)
+        }
+
+        fn part_one(input: &Self::Input) -> Answer {
+            input.iter().sum::<i64>().into()
+        }
+
+        fn auxiliary(input: &Self::Input, part: Part) -> Vec<(&'static str, Json)> {
+            match part {
+                Part::One => vec![("terms", input.len().into())],
+                Part::Two => vec![],
+            }
+        }
+    }
+
+    let solution = Solution::new::<Sum>(3, "test");
+    let reports = Report::solve(&solution, "1\n2\n", &Params::default(), &Part::ALL).unwrap();
+    let json: Vec<_> = reports
+        .iter()
+        .map(|report| {
+            let mut report = report.clone();
+            report.parse = Duration::from_nanos(5);
+            report.solve = Duration::from_nanos(7);
+            report.parse_allocations = None;
+            report.solve_allocations = None;
+            report.to_json().to_string()
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            r#"{"day":3,"author":"test","part":"one","answer":3,"auxiliary":{"terms":2},"parse_ns":5,"solve_ns":7}"#,
+            r#"{"day":3,"author":"test","part":"two","answer":null,"auxiliary":{},"parse_ns":5,"solve_ns":7}"#,
+        ],
+        json
+    );
+    assert!(Report::solve(&solution, "x\n", &Params::default(), &Part::ALL).is_err());
+
+    struct Panics;
+
+    impl Solver for Panics {
+        type Input = ();
+
+        fn parse(_: &str) -> Result<Self::Input> {
+            Ok(())
+        }
+
+        fn part_one(_: &Self::Input) -> Answer {
+            panic!("no answer")
+        }
+    }
+
+    let solution = Solution::new::<Panics>(3, "panics");
+    let e = Report::solve(&solution, "", &Params::default(), &Part::ALL).unwrap_err();
+    assert_eq!("panicked: no answer", e.to_string());
+
+    let mut report = reports[0].clone();
+    report.solve_allocations = Some(Allocations {
+        count: 1,
+        bytes: 16,
+        peak: 16,
+    });
+    assert!(report
+        .to_json()
+        .to_string()
+        .contains(r#""solve_allocations":{"count":1,"bytes":16,"peak":16}"#));
+    assert_eq!(Ok(Format::Json), "json".parse());
+    assert!("yaml".parse::<Format>().is_err());
+}
@@ -0,0 +1,207 @@
+// The shape every author's solution is ported to.
+use std::any::Any;
+
+use crate::json::Json;
+use crate::params::{Parameter, Params};
+use crate::{Answer, Part, Result};
+
+/// A solution to one day's puzzle, split into parsing and the two parts.
+///
+/// Implementors are usually unit structs named after their author; the parsed
+/// input is handed to both parts so it is only built once.
+pub trait Solver {
+    /// The puzzle input once parsed.
+    type Input;
+
+    /// The puzzle constants the solver lets the command line change.
+    const PARAMETERS: &'static [Parameter] = &[];
+
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    /// Parses `input` for the puzzle as changed by `params`, which hold a
+    /// value for every one of [`Solver::PARAMETERS`].
+    ///
+    /// Solvers with parameters implement this and have `parse` call it with
+    /// [`Params::defaults`].
+    fn parse_with(input: &str, _params: &Params) -> Result<Self::Input> {
+        Self::parse(input)
+    }
+
+    fn part_one(input: &Self::Input) -> Answer;
+
+    fn part_two(_input: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
+
+    /// Values besides the answer to `part` worth reporting, like the entries
+    /// that make it up. Only asked for once the part is solved, and not timed.
+    fn auxiliary(_input: &Self::Input, _part: Part) -> Vec<(&'static str, Json)> {
+        vec![]
+    }
+}
+
+/// Input parsed by a [`Solution`], ready to be handed back to it.
+pub struct Parsed(Box<dyn Any>);
+
+/// A [`Solver`] registered for a day and author, callable without knowing its
+/// input type.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub day: u8,
+    pub author: &'static str,
+    /// Which of an author's alternative implementations this is, if they
+    /// have more than one.
+    pub variant: Option<&'static str>,
+    parameters: &'static [Parameter],
+    parse: fn(&str, &Params) -> Result<Parsed>,
+    part_one: fn(&Parsed) -> Answer,
+    part_two: fn(&Parsed) -> Answer,
+    auxiliary: fn(&Parsed, Part) -> Vec<(&'static str, Json)>,
+}
+
+impl Solution {
+    pub fn new<S>(day: u8, author: &'static str) -> Solution
+    where
+        S: Solver,
+        S::Input: 'static,
+    {
+        Solution {
+            day,
+            author,
+            variant: None,
+            parameters: S::PARAMETERS,
+            parse: parse::<S>,
+            part_one: |parsed| S::part_one(downcast::<S>(parsed)),
+            part_two: |parsed| S::part_two(downcast::<S>(parsed)),
+            auxiliary: |parsed, part| S::auxiliary(downcast::<S>(parsed), part),
+        }
+    }
+
+    /// The same solution, marked as the author's `variant` implementation.
+    pub fn with_variant(self, variant: &'static str) -> Solution {
+        Solution {
+            variant: Some(variant),
+            ..self
+        }
+    }
+
+    /// The author, followed by the variant if there is one.
+    pub fn name(&self) -> String {
+        match self.variant {
+            Some(variant) => format!("{}/{}", self.author, variant),
+            None => self.author.to_string(),
+        }
+    }
+
+    /// The puzzle constants the solution lets the command line change.
+    pub fn parameters(&self) -> &'static [Parameter] {
+        self.parameters
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Parsed> {
+        self.parse_with(input, &Params::default())
+    }
+
+    /// Parses `input` with the parameters in `params` changed from their
+    /// defaults. Fails if `params` names a parameter the solution doesn't
+    /// take, or gives one a value it can't use.
+    pub fn parse_with(&self, input: &str, params: &Params) -> Result<Parsed> {
+        (self.parse)(input, &params.resolve(self.parameters)?)
+    }
+
+    /// Solves `part` from input previously parsed by this same solution.
+    pub fn solve(&self, parsed: &Parsed, part: Part) -> Answer {
+        match part {
+            Part::One => (self.part_one)(parsed),
+            Part::Two => (self.part_two)(parsed),
+        }
+    }
+
+    /// The solver's auxiliary values for `part`, from input previously parsed
+    /// by this same solution.
+    pub fn auxiliary(&self, parsed: &Parsed, part: Part) -> Vec<(&'static str, Json)> {
+        (self.auxiliary)(parsed, part)
+    }
+}
+
+fn parse<S>(input: &str, params: &Params) -> Result<Parsed>
+where
+    S: Solver,
+    S::Input: 'static,
+{
+    S::parse_with(input, params).map(|input| Parsed(Box::new(input)))
+}
+
+fn downcast<S>(parsed: &Parsed) -> &S::Input
+where
+    S: Solver,
+    S::Input: 'static,
+{
+    parsed
+        .0
+        .downcast_ref()
+        .expect("input was parsed by a different solution")
+}
+
+#[test]
+fn test_solution() {
+    struct Lines;
+
+    impl Solver for Lines {
+        type Input = Vec<String>;
+
+        fn parse(input: &str) -> Result<Self::Input> {
+            Ok(input.lines().map(String::from).collect())
+        }
+
+        fn part_one(input: &Self::Input) -> Answer {
+            input.len().into()
+        }
+    }
+
+    let solution = Solution::new::<Lines>(0, "test");
+    let parsed = solution.parse("a\nb\nc").unwrap();
+    assert_eq!(Answer::Int(3), solution.solve(&parsed, Part::One));
+    assert_eq!(Answer::Unsolved, solution.solve(&parsed, Part::Two));
+    assert!(solution.parameters().is_empty());
+
+    struct Longer;
+
+    impl Solver for Longer {
+        type Input = usize;
+
+        const PARAMETERS: &'static [Parameter] = &[Parameter {
+            name: "than",
+            default: "1",
+            about: "how long a line must be to count",
+        }];
+
+        fn parse(input: &str) -> Result<Self::Input> {
+            Self::parse_with(input, &Params::defaults(Self::PARAMETERS))
+        }
+
+        fn parse_with(input: &str, params: &Params) -> Result<Self::Input> {
+            let than: usize = params.get("than", "a length")?;
+            Ok(input.lines().filter(|line| line.len() > than).count())
+        }
+
+        fn part_one(input: &Self::Input) -> Answer {
+            (*input).into()
+        }
+    }
+
+    let solution = Solution::new::<Longer>(0, "test");
+    let count = |params: &[&str]| {
+        let params = Params::from_args(params).unwrap();
+        let parsed = solution.parse_with("a\nbb\nccc", &params)?;
+        Ok::<_, crate::Error>(solution.solve(&parsed, Part::One))
+    };
+    assert_eq!(Answer::Int(2), count(&[]).unwrap());
+    assert_eq!(Answer::Int(1), count(&["than=2"]).unwrap());
+    assert!(count(&["than=x"]).is_err());
+    assert!(count(&["thane=2"]).is_err());
+    let lines = Solution::new::<Lines>(0, "test");
+    assert!(lines
+        .parse_with("a", &Params::from_args(&["than=2"]).unwrap())
+        .is_err());
+}
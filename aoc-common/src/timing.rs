@@ -0,0 +1,9 @@
+// Wall-clock timing of a single call.
+use std::time::{Duration, Instant};
+
+/// Runs `f` once, returning its result along with how long it took.
+pub fn timed<T, F: FnOnce() -> T>(f: F) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
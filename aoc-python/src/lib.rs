@@ -0,0 +1,205 @@
+//! Every registered solution's parser and parts, callable from Python.
+//!
+//! A PyO3 extension module named `aoc`, built with `--features python`;
+//! `maturin develop` in this directory builds and installs it. Answers and
+//! auxiliary values come back as Python values, in the shape `--format json`
+//! prints them.
+#![cfg(feature = "python")]
+
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use pyo3::IntoPyObjectExt;
+
+use aoc::registry;
+use aoc_common::json::Json;
+use aoc_common::panics::{catch, silenced};
+use aoc_common::Part;
+
+/// Every registered solution's parser and parts.
+///
+/// >>> import aoc
+/// >>> parsed = aoc.Solution(1, 'matt').parse(open('day-1/examples/example.txt').read())
+/// >>> parsed.part_one()
+/// {'answer': 514579, 'auxiliary': {'entries': [1721, 299]}}
+#[pymodule]
+#[pyo3(name = "aoc")]
+pub fn aoc_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(solutions, m)?)?;
+    m.add_class::<Solution>()?;
+    m.add_class::<Parsed>()?;
+    Ok(())
+}
+
+/// Every solution and variant, each with its `day`, `author` and `variant`.
+#[pyfunction]
+fn solutions(py: Python<'_>) -> PyResult<Bound<'_, PyAny>> {
+    let solutions = registry::all()
+        .into_iter()
+        .chain(registry::variants())
+        .map(|s| {
+            Json::object(vec![
+                ("day", s.day.into()),
+                ("author", s.author.into()),
+                ("variant", s.variant.map_or(Json::Null, Json::from)),
+            ])
+        })
+        .collect();
+    to_python(py, &Json::Array(solutions))
+}
+
+/// The solution for `day` by `author`, or one of its variants if given.
+#[pyclass(module = "aoc", frozen)]
+struct Solution(aoc_common::Solution);
+
+#[pymethods]
+impl Solution {
+    #[new]
+    #[pyo3(signature = (day, author, variant=None))]
+    fn new(day: u8, author: &str, variant: Option<&str>) -> PyResult<Solution> {
+        let name = match variant {
+            Some(variant) => format!("{}/{}", author, variant),
+            None => author.to_string(),
+        };
+        registry::all()
+            .into_iter()
+            .chain(registry::variants())
+            .find(|s| s.day == day && s.name() == name)
+            .map(Solution)
+            .ok_or_else(|| {
+                PyValueError::new_err(format!("no solution by {} for day {}", name, day))
+            })
+    }
+
+    #[getter]
+    fn day(&self) -> u8 {
+        self.0.day
+    }
+
+    #[getter]
+    fn author(&self) -> &'static str {
+        self.0.author
+    }
+
+    #[getter]
+    fn variant(&self) -> Option<&'static str> {
+        self.0.variant
+    }
+
+    /// Parses `text`, raising `ValueError` if it doesn't parse.
+    fn parse(&self, text: &str) -> PyResult<Parsed> {
+        let parsed = silenced(|| catch(|| self.0.parse(text)))
+            .map_err(PyRuntimeError::new_err)?
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Parsed {
+            solution: self.0,
+            parsed,
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        let (day, author) = (self.0.day, self.0.author);
+        match self.0.variant {
+            Some(variant) => format!("Solution({}, '{}', '{}')", day, author, variant),
+            None => format!("Solution({}, '{}')", day, author),
+        }
+    }
+}
+
+/// An input parsed by a solution, ready to solve either part of.
+#[pyclass(module = "aoc", unsendable)]
+struct Parsed {
+    solution: aoc_common::Solution,
+    parsed: aoc_common::Parsed,
+}
+
+#[pymethods]
+impl Parsed {
+    /// The `answer` to `part` (`None` if unsolved) and its `auxiliary`
+    /// values. Raises `RuntimeError` if the solution panics.
+    fn solve<'py>(&self, py: Python<'py>, part: u8) -> PyResult<Bound<'py, PyAny>> {
+        let part = match part {
+            1 => Part::One,
+            2 => Part::Two,
+            _ => return Err(PyValueError::new_err(format!("there's no part {}", part))),
+        };
+        let solved = silenced(|| {
+            catch(|| {
+                let answer = self.solution.solve(&self.parsed, part);
+                let auxiliary = self.solution.auxiliary(&self.parsed, part);
+                Json::object(vec![
+                    ("answer", (&answer).into()),
+                    ("auxiliary", Json::object(auxiliary)),
+                ])
+            })
+        });
+        to_python(py, &solved.map_err(PyRuntimeError::new_err)?)
+    }
+
+    fn part_one<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        self.solve(py, 1)
+    }
+
+    fn part_two<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        self.solve(py, 2)
+    }
+}
+
+fn to_python<'py>(py: Python<'py>, json: &Json) -> PyResult<Bound<'py, PyAny>> {
+    match json {
+        Json::Null => Ok(py.None().into_bound(py)),
+        Json::Bool(b) => b.into_bound_py_any(py),
+        Json::Int(i) => i.into_bound_py_any(py),
+        Json::Str(s) => s.into_bound_py_any(py),
+        Json::Array(items) => {
+            let items = items
+                .iter()
+                .map(|item| to_python(py, item))
+                .collect::<PyResult<Vec<_>>>()?;
+            PyList::new(py, items)?.into_bound_py_any(py)
+        }
+        Json::Object(fields) => {
+            let dict = PyDict::new(py);
+            for (key, value) in fields {
+                dict.set_item(key, to_python(py, value)?)?;
+            }
+            dict.into_bound_py_any(py)
+        }
+    }
+}
+
+#[test]
+fn test_module() {
+    use std::ffi::CString;
+
+    pyo3::append_to_inittab!(aoc_module);
+    Python::initialize();
+    let code = CString::new(
+        r#"
+import aoc
+
+assert {'day': 8, 'author': 'matt', 'variant': None} in aoc.solutions()
+
+parsed = aoc.Solution(1, 'matt').parse('1721\n979\n366\n299\n675\n1456\n')
+one = parsed.part_one()
+assert one == {'answer': 514579, 'auxiliary': {'entries': [1721, 299]}}, one
+two = parsed.part_two()
+assert two == {'answer': None, 'auxiliary': {}}, two
+
+def raises(error, f, *args):
+    try:
+        f(*args)
+    except error as e:
+        return str(e)
+    raise AssertionError(f'{f} did not raise {error}')
+
+assert 'no part' in raises(ValueError, parsed.solve, 3)
+assert 'line 2' in raises(ValueError, aoc.Solution(1, 'matt').parse, '12\nx\n')
+message = raises(ValueError, aoc.Solution, 1, 'nobody')
+assert message == 'no solution by nobody for day 1', message
+assert repr(aoc.Solution(3, 'vickz84259', 'bit-map')) == "Solution(3, 'vickz84259', 'bit-map')"
+"#,
+    )
+    .unwrap();
+    Python::attach(|py| py.run(&code, None, None)).unwrap();
+}
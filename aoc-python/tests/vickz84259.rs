@@ -0,0 +1,94 @@
+// vickz84259's original Python solution to day 1, run in-process on the same
+// expense reports as the Rust solutions are through the `aoc` module, which
+// must find the same answers.
+#![cfg(feature = "python")]
+
+use std::ffi::CString;
+
+use pyo3::prelude::*;
+
+use aoc::registry;
+use aoc_common::generate::Rng;
+use aoc_python::aoc_module;
+
+const SEED: u64 = 1;
+const CASES: u64 = 10;
+
+/// Runs the script's parts on an input, and the solutions on it through the
+/// `aoc` module.
+const PARITY: &str = r#"
+import contextlib
+import io
+
+import aoc
+import vickz84259
+
+
+def script(text):
+    """The answers the script prints, in order: parts one and two, each
+    solved two ways."""
+    entries = set(int(entry) for entry in text.split())
+    printed = io.StringIO()
+    with contextlib.redirect_stdout(printed):
+        vickz84259.part_1(entries)
+        vickz84259.part_1_alt(entries)
+        vickz84259.part_2(entries)
+        vickz84259.part_2_alt(entries)
+    return [int(line[len('Answer: '):])
+            for line in printed.getvalue().splitlines()
+            if line.startswith('Answer: ')]
+
+
+def solution(author, text):
+    parsed = aoc.Solution(1, author).parse(text)
+    return [parsed.part_one()['answer'], parsed.part_two()['answer']]
+"#;
+
+/// Imports `code` as the module `name`.
+fn module<'py>(py: Python<'py>, code: &str, name: &str) -> Bound<'py, PyModule> {
+    let file = format!("{}.py", name);
+    let [code, file, name] = [code, &file, name].map(|s| CString::new(s).unwrap());
+    PyModule::from_code(py, &code, &file, &name).unwrap()
+}
+
+#[test]
+fn test_parity() {
+    pyo3::append_to_inittab!(aoc_module);
+    Python::initialize();
+
+    let example = include_str!("../../day-1/examples/example.txt").to_string();
+    let generate = registry::generator(1).unwrap();
+    let mut rng = Rng::new(SEED);
+    let generated: Vec<_> = (0..CASES).map(|_| generate(&mut rng, 200).input).collect();
+
+    Python::attach(|py| {
+        module(
+            py,
+            include_str!("../../day-1/src/vickz84259.py"),
+            "vickz84259",
+        );
+        let parity = module(py, PARITY, "parity");
+
+        for input in std::iter::once(example).chain(generated) {
+            let answers: Vec<i64> = parity
+                .call_method1("script", (&input,))
+                .and_then(|answers| answers.extract())
+                .unwrap();
+            assert_eq!(4, answers.len(), "{}", input);
+            let (one, two) = (answers[0], answers[2]);
+            assert_eq!((one, two), (answers[1], answers[3]), "{}", input);
+
+            // matt hasn't solved part two.
+            for (author, expected) in [
+                ("vickz84259", [Some(one), Some(two)]),
+                ("matt", [Some(one), None]),
+            ] {
+                let solved: [Option<i64>; 2] = parity
+                    .call_method1("solution", (author, &input))
+                    .and_then(|answers| answers.extract())
+                    .unwrap();
+                assert_eq!(expected, solved, "{} on\n{}", author, input);
+            }
+        }
+    });
+}
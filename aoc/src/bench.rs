@@ -0,0 +1,155 @@
+// Benchmarking each phase of a solution on its own, so that parsing, part one
+// and part two can be told apart and compared across authors.
+use std::fmt;
+use std::iter;
+
+use aoc_common::allocations::{self, Allocations};
+use aoc_common::bench::{self, Config, Stats};
+use aoc_common::panics::{catch, silenced};
+use aoc_common::{Answer, Error, Part, Result, Solution};
+
+use crate::verified;
+
+/// Runs `f` once, counting what it allocates, and turning a panic into an
+/// error.
+fn once<T, F: FnOnce() -> T>(f: F) -> Result<(T, Option<Allocations>)> {
+    silenced(|| catch(|| allocations::counted(f))).map_err(Error::Panic)
+}
+
+/// How long a solution takes on one input.
+pub struct Benchmark {
+    pub day: u8,
+    /// The solution's author, followed by its variant if it has one.
+    pub name: String,
+    /// The input's hash, as [`verified::hash`] gives it.
+    pub input_hash: String,
+    pub parse: Stats,
+    /// The parts that were measured; `None` if the part is unsolved.
+    pub parts: Vec<(Part, Option<Stats>)>,
+    /// What each phase allocates in one call, by the phase's name as in
+    /// [`phases`](Benchmark::phases); empty unless allocations are counted.
+    pub allocations: Vec<(String, Allocations)>,
+}
+
+impl Benchmark {
+    /// Measures `parts` of `solution` on `input`, after parsing it.
+    pub fn run(
+        solution: &Solution,
+        input: &str,
+        parts: &[Part],
+        config: &Config,
+    ) -> Result<Benchmark> {
+        // Each phase runs once before it's measured, catching any panic so
+        // that it's an error for this solution alone, like unparseable input.
+        let (parsed, parse_allocations) = once(|| solution.parse(input))?;
+        let parsed = parsed?;
+        let parse = bench::measure(config, || solution.parse(input));
+        let mut allocations: Vec<_> = parse_allocations
+            .map(|allocations| ("Parse".to_string(), allocations))
+            .into_iter()
+            .collect();
+        let mut measured = vec![];
+        for &part in parts {
+            let stats = match once(|| solution.solve(&parsed, part))? {
+                (Answer::Unsolved, _) => None,
+                (_, counted) => {
+                    if let Some(counted) = counted {
+                        allocations.push((format!("Part {}", part), counted));
+                    }
+                    Some(bench::measure(config, || solution.solve(&parsed, part)))
+                }
+            };
+            measured.push((part, stats));
+        }
+
+        Ok(Benchmark {
+            day: solution.day,
+            name: solution.name(),
+            input_hash: verified::hash(input),
+            parse,
+            parts: measured,
+            allocations,
+        })
+    }
+
+    /// What the phase named `phase` allocates, if allocations are counted.
+    pub fn allocations(&self, phase: &str) -> Option<&Allocations> {
+        self.allocations
+            .iter()
+            .find(|(name, _)| name == phase)
+            .map(|(_, allocations)| allocations)
+    }
+
+    /// Each phase that was measured, with its name.
+    pub fn phases(&self) -> Vec<(String, &Stats)> {
+        let parts = self.parts.iter().filter_map(|(part, stats)| {
+            stats
+                .as_ref()
+                .map(|stats| (format!("Part {}", part), stats))
+        });
+        iter::once(("Parse".to_string(), &self.parse))
+            .chain(parts)
+            .collect()
+    }
+}
+
+impl fmt::Display for Benchmark {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Day {} ({})", self.day, self.name)?;
+        let parts = self
+            .parts
+            .iter()
+            .map(|(part, stats)| (format!("Part {}", part), stats.as_ref()));
+        for (phase, stats) in iter::once(("Parse".to_string(), Some(&self.parse))).chain(parts) {
+            let label = format!("{}:", phase);
+            match (stats, self.allocations(&phase)) {
+                (Some(stats), Some(allocations)) => {
+                    writeln!(f, "    {:<10} {}  {}", label, stats, allocations)?
+                }
+                (Some(stats), None) => writeln!(f, "    {:<10} {}", label, stats)?,
+                (None, _) => writeln!(f, "    {:<10} unsolved", label)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Benchmarks of the same day ranked against each other, phase by phase.
+pub struct HeadToHead<'a>(pub &'a [Benchmark]);
+
+impl fmt::Display for HeadToHead<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut phases: Vec<String> = vec![];
+        for benchmark in self.0 {
+            for (phase, _) in benchmark.phases() {
+                if !phases.contains(&phase) {
+                    phases.push(phase);
+                }
+            }
+        }
+
+        for phase in phases {
+            let mut ranked: Vec<_> = self
+                .0
+                .iter()
+                .filter_map(|benchmark| {
+                    let (_, stats) = benchmark.phases().into_iter().find(|(p, _)| *p == phase)?;
+                    Some((&benchmark.name, stats.median()))
+                })
+                .collect();
+            ranked.sort_by_key(|(_, median)| *median);
+
+            writeln!(f, "{}:", phase)?;
+            let fastest = ranked[0].1.as_secs_f64();
+            for (name, median) in ranked {
+                let ratio = if fastest > 0.0 {
+                    median.as_secs_f64() / fastest
+                } else {
+                    1.0
+                };
+                writeln!(f, "    {:<28} {:>10.1?}  {:>6.2}x", name, median, ratio)?;
+            }
+        }
+        Ok(())
+    }
+}
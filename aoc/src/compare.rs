@@ -0,0 +1,148 @@
+// Differential checking: run every author's solution for a day on the same
+// input and see whether they agree.
+use std::fmt;
+
+use aoc_common::panics::{catch, silenced};
+use aoc_common::params::Params;
+use aoc_common::{Answer, Part, Solution};
+
+/// One part's answer, or why the solution couldn't produce it.
+pub type Outcome = Result<Answer, String>;
+
+/// The answers of every solution for a day, on a single input.
+pub struct Comparison {
+    /// Each author with their outcome for part one and part two.
+    pub rows: Vec<(&'static str, [Outcome; 2])>,
+}
+
+impl Comparison {
+    /// Runs each of `solutions` on `input`, with `params` changed from their
+    /// defaults.
+    ///
+    /// A solution that fails to parse or panics is recorded as an error rather
+    /// than stopping the whole comparison.
+    pub fn run(solutions: &[Solution], input: &str, params: &Params) -> Comparison {
+        let rows = silenced(|| {
+            solutions
+                .iter()
+                .map(|solution| (solution.author, outcomes(solution, input, params)))
+                .collect()
+        });
+        Comparison { rows }
+    }
+
+    /// The parts for which two solutions gave different answers, including
+    /// one leaving the part unsolved where another answered it.
+    ///
+    /// Parts that failed are left out.
+    pub fn disagreements(&self) -> Vec<Part> {
+        Part::ALL
+            .iter()
+            .copied()
+            .filter(|part| {
+                let mut answers = self
+                    .rows
+                    .iter()
+                    .filter_map(|(_, outcomes)| outcomes[index(*part)].as_ref().ok());
+                match answers.next() {
+                    Some(first) => answers.any(|answer| answer != first),
+                    None => false,
+                }
+            })
+            .collect()
+    }
+
+    /// Whether any solution failed to produce an answer.
+    pub fn has_errors(&self) -> bool {
+        self.rows
+            .iter()
+            .any(|(_, outcomes)| outcomes.iter().any(Result::is_err))
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<12}  {:<16}  {:<16}",
+            "author", "part one", "part two"
+        )?;
+        for (author, [one, two]) in &self.rows {
+            writeln!(f, "{:<12}  {:<16}  {:<16}", author, cell(one), cell(two))?;
+        }
+        for part in self.disagreements() {
+            writeln!(f, "part {} differs", part.to_string().to_lowercase())?;
+        }
+        for (author, outcomes) in &self.rows {
+            // A parse error fails both parts the same way; say so only once.
+            if let [Err(one), Err(two)] = outcomes {
+                if one == two {
+                    writeln!(f, "{}: {}", author, one)?;
+                    continue;
+                }
+            }
+            for (part, outcome) in Part::ALL.iter().zip(outcomes) {
+                if let Err(e) = outcome {
+                    writeln!(
+                        f,
+                        "{} part {}: {}",
+                        author,
+                        part.to_string().to_lowercase(),
+                        e
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn cell(outcome: &Outcome) -> String {
+    match outcome {
+        Ok(answer) => answer.to_string(),
+        Err(_) => "error".to_string(),
+    }
+}
+
+fn index(part: Part) -> usize {
+    match part {
+        Part::One => 0,
+        Part::Two => 1,
+    }
+}
+
+/// Solves both parts of `input` with `solution`, catching any panic.
+pub(crate) fn outcomes(solution: &Solution, input: &str, params: &Params) -> [Outcome; 2] {
+    let parsed = match catch(|| solution.parse_with(input, params)) {
+        Ok(Ok(parsed)) => parsed,
+        Ok(Err(e)) => return [Err(e.to_string()), Err(e.to_string())],
+        Err(e) => return [Err(e.clone()), Err(e)],
+    };
+    [
+        catch(|| solution.solve(&parsed, Part::One)),
+        catch(|| solution.solve(&parsed, Part::Two)),
+    ]
+}
+
+#[test]
+fn test_disagreements() {
+    let comparison = Comparison {
+        rows: vec![
+            ("a", [Ok(Answer::Int(1)), Ok(Answer::Int(2))]),
+            ("b", [Ok(Answer::Int(1)), Ok(Answer::Int(3))]),
+            ("c", [Ok(Answer::Unsolved), Ok(Answer::Unsolved)]),
+            ("d", [Ok(Answer::Int(1)), Err("panicked".to_string())]),
+        ],
+    };
+    assert_eq!(vec![Part::One, Part::Two], comparison.disagreements());
+    assert!(comparison.has_errors());
+
+    let comparison = Comparison {
+        rows: vec![
+            ("a", [Ok(Answer::Int(1)), Ok(Answer::Unsolved)]),
+            ("b", [Ok(Answer::Int(1)), Ok(Answer::Unsolved)]),
+        ],
+    };
+    assert!(comparison.disagreements().is_empty());
+    assert!(!comparison.has_errors());
+}
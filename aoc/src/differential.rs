@@ -0,0 +1,428 @@
+// Differential testing: random inputs fed to every solution of a day until
+// they disagree, and the input they disagree on shrunk to as few lines as
+// still make them disagree.
+//
+// Days 1-6 have two authors to compare with each other. Days 7 and 8 only
+// have one, so they're compared with the models in `reference` instead. A
+// part is only compared on inputs that meet what its puzzle promises (e.g.
+// exactly one pair of entries summing to 2020), so that no solution is blamed
+// for an input the puzzle never gives.
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use aoc_common::generate::{Generator, Rng};
+use aoc_common::panics::silenced;
+use aoc_common::params::Params;
+use aoc_common::{Answer, Part, Solution, Solver};
+
+use crate::compare::{outcomes, Outcome};
+use crate::reference::{self, Bags, Exit, Handheld};
+use crate::registry;
+
+/// How many times each solution is run on an input, so that solutions that
+/// only sometimes get it wrong (e.g. depending on the order a `HashSet` is
+/// iterated in) are caught too.
+const RUNS: usize = 8;
+
+/// How long a solution may take on an input before it's assumed to be stuck.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Makes a random input.
+type Generate = fn(&mut Rng) -> String;
+
+/// Whether an input is fair to compare a part on.
+type Valid = fn(&str, Part) -> bool;
+
+/// How to make random inputs for a day, and which parts they're fair for.
+#[derive(Clone, Copy)]
+pub struct Property {
+    pub day: u8,
+    generate: Generate,
+    valid: Valid,
+}
+
+impl Property {
+    pub fn for_day(day: u8) -> Option<Property> {
+        let (generate, valid): (Generate, Valid) = match day {
+            1 => (expense_report, valid_expense_report),
+            2 => (
+                |rng| sized(rng, day_2::generate::input, 1..=30),
+                |_, _| true,
+            ),
+            3 => (map, valid_map),
+            4 => (
+                |rng| sized(rng, day_4::generate::input, 1..=12),
+                valid_records,
+            ),
+            5 => (flight, valid_boarding_passes),
+            6 => (
+                |rng| sized(rng, day_6::generate::input, 1..=12),
+                valid_records,
+            ),
+            7 => (|rng| sized(rng, day_7::generate::input, 1..=40), valid_bags),
+            8 => (
+                |rng| sized(rng, day_8::generate::input, 8..=40),
+                valid_program,
+            ),
+            _ => return None,
+        };
+        Some(Property {
+            day,
+            generate,
+            valid,
+        })
+    }
+
+    /// What to compare: every solution and variant for the day, and the
+    /// reference model if there's only one author.
+    pub fn solutions(&self) -> Vec<Solution> {
+        let mut solutions = registry::find(self.day, None);
+        solutions.extend(registry::find_variants(self.day, None));
+        match self.day {
+            7 => solutions.push(Solution::new::<Bags>(7, "reference")),
+            8 => solutions.push(Solution::new::<Handheld>(8, "reference")),
+            _ => {}
+        }
+        solutions
+    }
+
+    /// Tries `cases` random inputs, the first made from `seed` and each of the
+    /// others from the seed after, stopping at the first the solutions
+    /// disagree on.
+    pub fn check(&self, solutions: &[Solution], seed: u64, cases: u64) -> Option<Counterexample> {
+        silenced(|| {
+            (seed..seed.saturating_add(cases)).find_map(|seed| {
+                let input = (self.generate)(&mut Rng::new(seed));
+                Part::ALL.iter().find_map(|&part| {
+                    let outcomes = self.disagreement(solutions, &input, part)?;
+                    Some(self.shrink(solutions, seed, &input, part, outcomes))
+                })
+            })
+        })
+    }
+
+    /// Each solution's outcomes for `part` of `input`, if they disagree.
+    fn disagreement(
+        &self,
+        solutions: &[Solution],
+        input: &str,
+        part: Part,
+    ) -> Option<Vec<(String, Vec<Outcome>)>> {
+        if !(self.valid)(input, part) {
+            return None;
+        }
+        let outcomes: Vec<(String, Vec<Outcome>)> = solutions
+            .iter()
+            .map(|solution| {
+                let mut runs: Vec<Outcome> = vec![];
+                for _ in 0..RUNS {
+                    let [one, two] = run(*solution, input);
+                    let outcome = if part == Part::One { one } else { two };
+                    if !runs.iter().any(|run| same(run, &outcome)) {
+                        runs.push(outcome);
+                    }
+                }
+                (solution.name(), runs)
+            })
+            .collect();
+
+        let mut answered = outcomes
+            .iter()
+            .flat_map(|(_, runs)| runs)
+            .filter(|outcome| !matches!(outcome, Ok(Answer::Unsolved)));
+        let first = answered.next()?;
+        if answered.any(|outcome| !same(outcome, first)) {
+            Some(outcomes)
+        } else {
+            None
+        }
+    }
+
+    /// Removes as many lines from `input` as it can while the solutions still
+    /// disagree on it.
+    fn shrink(
+        &self,
+        solutions: &[Solution],
+        seed: u64,
+        input: &str,
+        part: Part,
+        mut outcomes: Vec<(String, Vec<Outcome>)>,
+    ) -> Counterexample {
+        let mut lines: Vec<&str> = input.lines().collect();
+        let mut chunk = (lines.len() / 2).max(1);
+        loop {
+            let mut shrunk = false;
+            let mut start = 0;
+            while start < lines.len() {
+                let end = (start + chunk).min(lines.len());
+                let candidate: Vec<&str> = [&lines[..start], &lines[end..]].concat();
+                match self.disagreement(solutions, &join(&candidate), part) {
+                    Some(disagreement) => {
+                        lines = candidate;
+                        outcomes = disagreement;
+                        shrunk = true;
+                    }
+                    None => start = end,
+                }
+            }
+            if chunk > 1 {
+                chunk /= 2;
+            } else if !shrunk {
+                break;
+            }
+        }
+
+        Counterexample {
+            day: self.day,
+            part,
+            seed,
+            input: join(&lines),
+            outcomes,
+        }
+    }
+}
+
+/// An input on which solutions disagree about a part.
+#[derive(Debug, Clone)]
+pub struct Counterexample {
+    pub day: u8,
+    pub part: Part,
+    /// The seed the input was generated from, before it was shrunk.
+    pub seed: u64,
+    pub input: String,
+    /// Each solution's outcomes; more than one if it gave different ones on
+    /// different runs.
+    pub outcomes: Vec<(String, Vec<Outcome>)>,
+}
+
+impl fmt::Display for Counterexample {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Day {} part {} disagrees on this input (shrunk from seed {}):",
+            self.day,
+            self.part.to_string().to_lowercase(),
+            self.seed
+        )?;
+        for line in self.input.lines() {
+            writeln!(f, "    {}", line)?;
+        }
+        for (name, runs) in &self.outcomes {
+            let runs: Vec<_> = runs
+                .iter()
+                .map(|outcome| match outcome {
+                    Ok(answer) => answer.to_string(),
+                    Err(e) => e.lines().next().unwrap_or_default().to_string(),
+                })
+                .collect();
+            writeln!(f, "{:<28} {}", name, runs.join(" or "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Solves both parts, giving up on a solution that seems to be stuck. Its
+/// thread is left running.
+fn run(solution: Solution, input: &str) -> [Outcome; 2] {
+    let (sender, receiver) = mpsc::channel();
+    let input = input.to_string();
+    thread::spawn(move || sender.send(outcomes(&solution, &input, &Params::default())));
+    receiver.recv_timeout(TIMEOUT).unwrap_or_else(|_| {
+        let stuck = format!("took longer than {:?}", TIMEOUT);
+        [Err(stuck.clone()), Err(stuck)]
+    })
+}
+
+/// Whether two outcomes agree. Failures all agree with each other, as
+/// authors word their errors differently.
+fn same(a: &Outcome, b: &Outcome) -> bool {
+    match (a, b) {
+        (Ok(a), Ok(b)) => a == b,
+        (Err(_), Err(_)) => true,
+        _ => false,
+    }
+}
+
+fn join(lines: &[&str]) -> String {
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+/// An input from `generator`, of a size in `sizes`.
+fn sized(rng: &mut Rng, generator: Generator, sizes: RangeInclusive<usize>) -> String {
+    let size = rng.range(sizes);
+    generator(rng, size).input
+}
+
+/// A report that might have an entry of 1010, which mustn't be paired with
+/// itself.
+fn expense_report(rng: &mut Rng) -> String {
+    let input = sized(rng, day_1::generate::input, 5..=20);
+    let mut lines: Vec<&str> = input.lines().collect();
+    if rng.chance(0.5) {
+        let at = rng.range(0..=lines.len());
+        lines.insert(at, "1010");
+    }
+    join(&lines)
+}
+
+/// Exactly one pair of entries sums to 2020 for part one, and exactly one
+/// triple for part two.
+fn valid_expense_report(input: &str, part: Part) -> bool {
+    let entries: Vec<i64> = match input.lines().map(str::parse).collect() {
+        Ok(entries) => entries,
+        Err(_) => return false,
+    };
+    let n = entries.len();
+    let mut sums = 0;
+    for i in 0..n {
+        for j in i + 1..n {
+            match part {
+                Part::One => sums += (entries[i] + entries[j] == 2020) as usize,
+                Part::Two => {
+                    for k in j + 1..n {
+                        sums += (entries[i] + entries[j] + entries[k] == 2020) as usize;
+                    }
+                }
+            }
+        }
+    }
+    sums == 1
+}
+
+/// A short flight, anywhere in the plane.
+fn flight(rng: &mut Rng) -> String {
+    let first = rng.range(0..=1000);
+    let last = first + rng.range(2..=23);
+    day_5::generate::flight(rng, first, last).input
+}
+
+/// A map of any width, including ones too wide to fit in a machine word.
+fn map(rng: &mut Rng) -> String {
+    let map = day_3::generate::Map {
+        width: rng.range(1..=70),
+        height: rng.range(1..=40),
+        trees: rng.fraction(),
+    };
+    day_3::generate::map(rng, &map).input
+}
+
+/// At least one row, all as wide as each other, starting on an open square.
+fn valid_map(input: &str, _: Part) -> bool {
+    let width = input.lines().next().map_or(0, str::len);
+    input.starts_with('.') && input.lines().all(|line| line.len() == width)
+}
+
+/// At least one record, and no empty ones.
+fn valid_records(input: &str, _: Part) -> bool {
+    !input.trim().is_empty() && !input.starts_with('\n') && !input.contains("\n\n\n")
+}
+
+/// For part two, every seat from the first to the last but one.
+fn valid_boarding_passes(input: &str, part: Part) -> bool {
+    let seat = |pass: &str| {
+        pass.chars().try_fold(0, |seat, c| match c {
+            'F' | 'L' => Some(seat * 2),
+            'B' | 'R' => Some(seat * 2 + 1),
+            _ => None,
+        })
+    };
+    let mut seats: Vec<usize> = match input.lines().map(seat).collect() {
+        Some(seats) => seats,
+        None => return false,
+    };
+    seats.sort_unstable();
+    match (part, seats.first(), seats.last()) {
+        (_, None, _) | (_, _, None) => false,
+        (Part::One, _, _) => true,
+        (Part::Two, Some(first), Some(last)) => {
+            seats.windows(2).all(|w| w[0] != w[1]) && last - first == seats.len()
+        }
+    }
+}
+
+fn valid_bags(input: &str, _: Part) -> bool {
+    Bags::parse(input).is_ok_and(|rules| reference::valid_bags(&rules))
+}
+
+/// The program loops, and flipping one instruction, and only one, makes it
+/// terminate. No instruction may jump out of the program, flipped or not.
+fn valid_program(input: &str, part: Part) -> bool {
+    let program = match Handheld::parse(input) {
+        Ok(program) => program,
+        Err(_) => return false,
+    };
+    let in_bounds = |program: &[(String, i64)]| {
+        program
+            .iter()
+            .enumerate()
+            .all(|(i, (operation, argument))| {
+                operation == "acc" || (0..=program.len() as i64).contains(&(i as i64 + argument))
+            })
+    };
+    matches!(reference::run(&program), Exit::Looped(_))
+        && in_bounds(&program)
+        && (part == Part::One || reference::fixes(&program).len() == 1)
+}
+
+#[test]
+fn test_shrink() {
+    use aoc_common::Result;
+
+    // Wrong whenever the input has a line of 7 in it.
+    struct Sevens;
+    impl Solver for Sevens {
+        type Input = Vec<u32>;
+        fn parse(input: &str) -> Result<Vec<u32>> {
+            Ok(input.lines().map(|line| line.parse().unwrap()).collect())
+        }
+        fn part_one(numbers: &Vec<u32>) -> Answer {
+            (numbers.len() + numbers.contains(&7) as usize).into()
+        }
+    }
+    struct Count;
+    impl Solver for Count {
+        type Input = usize;
+        fn parse(input: &str) -> Result<usize> {
+            Ok(input.lines().count())
+        }
+        fn part_one(count: &usize) -> Answer {
+            (*count).into()
+        }
+    }
+
+    let property = Property {
+        day: 0,
+        generate: |rng| {
+            (0..rng.range(10..=20))
+                .map(|_| format!("{}\n", rng.range(0..=9)))
+                .collect()
+        },
+        valid: |_, _| true,
+    };
+    let solutions = [
+        Solution::new::<Sevens>(0, "sevens"),
+        Solution::new::<Count>(0, "count"),
+    ];
+    let counterexample = property.check(&solutions, 0, 100).unwrap();
+    assert_eq!(
+        (Part::One, "7\n"),
+        (counterexample.part, counterexample.input.as_str())
+    );
+    assert_eq!(
+        vec![vec![Ok(Answer::Int(2))], vec![Ok(Answer::Int(1))]],
+        counterexample
+            .outcomes
+            .into_iter()
+            .map(|(_, runs)| runs)
+            .collect::<Vec<_>>()
+    );
+
+    let agreeing = [
+        Solution::new::<Count>(0, "count"),
+        Solution::new::<Count>(0, "again"),
+    ];
+    assert!(property.check(&agreeing, 0, 20).is_none());
+}
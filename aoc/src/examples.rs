@@ -0,0 +1,187 @@
+// The worked examples from each puzzle's description, with their answers, so
+// that every author's solution can be checked against them.
+//
+// A day's examples live in its `examples` folder: `<name>.txt` is the input
+// and `<name>.answers` has the answers, one part per line, e.g. `one: 514579`.
+// Parts the description gives no answer for are left out.
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use aoc_common::generate::Generated;
+use aoc_common::panics::{catch, silenced};
+use aoc_common::{parse, Answer, ParseError, Part, Result, Solution};
+
+use crate::workspace_dir;
+
+/// An example input and the answers expected for it.
+#[derive(Debug, Clone)]
+pub struct Example {
+    pub day: u8,
+    pub name: String,
+    pub input: String,
+    pub answers: Vec<(Part, String)>,
+}
+
+/// How a solution did on one part of an example.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Passed,
+    /// The solution doesn't solve this part yet.
+    Unsolved,
+    Failed(String),
+}
+
+impl Example {
+    /// Runs `solution` on the example, checking each part with an answer.
+    pub fn check(&self, solution: &Solution) -> Vec<(Part, Check)> {
+        let parsed = match silenced(|| catch(|| solution.parse(&self.input))) {
+            Ok(Ok(parsed)) => parsed,
+            Ok(Err(e)) => return self.fail_all(&e.to_string()),
+            Err(e) => return self.fail_all(&e),
+        };
+        self.answers
+            .iter()
+            .map(|(part, expected)| {
+                let check = match silenced(|| catch(|| solution.solve(&parsed, *part))) {
+                    Ok(Answer::Unsolved) => Check::Unsolved,
+                    Ok(answer) if answer.to_string() == *expected => Check::Passed,
+                    Ok(answer) => Check::Failed(format!("expected {}, got {}", expected, answer)),
+                    Err(e) => Check::Failed(e),
+                };
+                (*part, check)
+            })
+            .collect()
+    }
+
+    fn fail_all(&self, reason: &str) -> Vec<(Part, Check)> {
+        self.answers
+            .iter()
+            .map(|(part, _)| (*part, Check::Failed(reason.to_string())))
+            .collect()
+    }
+}
+
+impl fmt::Display for Example {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "day-{}/examples/{}", self.day, self.name)
+    }
+}
+
+/// Where the examples for `day` are kept.
+pub fn dir(day: u8) -> PathBuf {
+    workspace_dir()
+        .join(format!("day-{}", day))
+        .join("examples")
+}
+
+/// Every example for `day`, in order of name.
+pub fn load(day: u8) -> Result<Vec<Example>> {
+    let dir = dir(day);
+    let mut names = vec![];
+    for entry in fs::read_dir(&dir).map_err(naming(&dir))? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "txt") {
+            if let Some(name) = path.file_stem() {
+                names.push(name.to_string_lossy().into_owned());
+            }
+        }
+    }
+    names.sort();
+
+    names
+        .into_iter()
+        .map(|name| {
+            let input = read(&dir.join(format!("{}.txt", name)))?;
+            let answers_path = dir.join(format!("{}.answers", name));
+            let answers = read_answers(&answers_path)?;
+            Ok(Example {
+                day,
+                name,
+                input,
+                answers,
+            })
+        })
+        .collect()
+}
+
+/// Saves a generated input as an example: the input to `<stem>.txt` and its
+/// answers to `<stem>.answers`.
+pub fn save(stem: &Path, generated: &Generated) -> io::Result<()> {
+    let with_extension = |extension: &str| {
+        let mut path = stem.as_os_str().to_owned();
+        path.push(extension);
+        PathBuf::from(path)
+    };
+    let (input, answers) = (with_extension(".txt"), with_extension(".answers"));
+    fs::write(&input, &generated.input).map_err(naming(&input))?;
+    fs::write(&answers, answers_file(generated)).map_err(naming(&answers))
+}
+
+/// The answers of a generated input, as they'd be written in an example.
+pub fn answers_file(generated: &Generated) -> String {
+    Part::ALL
+        .iter()
+        .map(|&part| {
+            let name = part.to_string().to_lowercase();
+            format!("{}: {}\n", name, generated.answer(part))
+        })
+        .collect()
+}
+
+fn read(path: &Path) -> io::Result<String> {
+    fs::read_to_string(path).map_err(naming(path))
+}
+
+/// Adds `path` to the message of an error about it.
+fn naming(path: &Path) -> impl FnOnce(io::Error) -> io::Error + '_ {
+    move |e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e))
+}
+
+fn read_answers(path: &Path) -> Result<Vec<(Part, String)>> {
+    let text = read(path)?;
+    let answers: Vec<_> = parse::lines(&text, |line| {
+        if line.starts_with('#') || line.trim().is_empty() {
+            return Ok(None);
+        }
+        let (part, answer) = parse::split_once(line, line, ":", "`<part>: <answer>`")?;
+        let part = parse::value(line, part.trim(), "`one` or `two`")?;
+        match answer.trim() {
+            "" => Err(ParseError::missing(line, "an answer")),
+            answer => Ok(Some((part, answer.to_string()))),
+        }
+    })
+    .map_err(|e| e.in_file(path.display()))?;
+    Ok(answers.into_iter().flatten().collect())
+}
+
+#[test]
+fn test_check() {
+    let example = Example {
+        day: 1,
+        name: "test".to_string(),
+        input: "1721\n979\n366\n299\n675\n1456\n".to_string(),
+        answers: vec![
+            (Part::One, "514579".to_string()),
+            (Part::Two, "241861950".to_string()),
+        ],
+    };
+    let matt = Solution::new::<day_1::matt::Matt>(1, "matt");
+    assert_eq!(
+        vec![(Part::One, Check::Passed), (Part::Two, Check::Unsolved)],
+        example.check(&matt)
+    );
+
+    let wrong = Example {
+        answers: vec![(Part::One, "42".to_string())],
+        ..example
+    };
+    assert_eq!(
+        vec![(
+            Part::One,
+            Check::Failed("expected 42, got 514579".to_string())
+        )],
+        wrong.check(&matt)
+    );
+}
@@ -0,0 +1,348 @@
+// A local record of benchmark results over time, to catch solutions that got
+// slower.
+//
+// Each benchmarked phase is one tab-separated line: the git commit it was
+// measured at, when, the day, the solution's name, the phase, the median and
+// spread in nanoseconds, and a hash of the input. Only measurements on the
+// same input are compared. Lines starting with `#` are comments.
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::iter;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use aoc_common::{parse, ParseError, Part, Result};
+
+use crate::bench::Benchmark;
+use crate::workspace_dir;
+
+const HEADER: &str = "# commit\ttime\tday\tsolution\tpart\tmedian_ns\tspread_ns\tinput_hash";
+
+/// What was measured: parsing, or solving one of the parts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Phase {
+    Parse,
+    Solve(Part),
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Parse => f.pad("parse"),
+            Phase::Solve(part) => f.pad(&part.to_string().to_lowercase()),
+        }
+    }
+}
+
+impl FromStr for Phase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Phase, Self::Err> {
+        match s {
+            "parse" => Ok(Phase::Parse),
+            part => part.parse().map(Phase::Solve),
+        }
+    }
+}
+
+/// One phase of one solution, measured at one commit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub commit: String,
+    /// Seconds since the Unix epoch.
+    pub time: u64,
+    pub day: u8,
+    /// The solution's author, followed by its variant if it has one.
+    pub solution: String,
+    pub phase: Phase,
+    pub median_ns: u64,
+    pub spread_ns: u64,
+    /// The input's hash, as [`verified::hash`](crate::verified::hash) gives
+    /// it; empty for records saved before inputs were told apart.
+    pub input_hash: String,
+}
+
+impl Record {
+    /// A record of every phase measured by `benchmark`.
+    pub fn from_benchmark(benchmark: &Benchmark, commit: &str, time: u64) -> Vec<Record> {
+        let parts = benchmark
+            .parts
+            .iter()
+            .filter_map(|(part, stats)| stats.as_ref().map(|stats| (Phase::Solve(*part), stats)));
+        iter::once((Phase::Parse, &benchmark.parse))
+            .chain(parts)
+            .map(|(phase, stats)| Record {
+                commit: commit.to_string(),
+                time,
+                day: benchmark.day,
+                solution: benchmark.name.clone(),
+                phase,
+                median_ns: stats.median().as_nanos() as u64,
+                spread_ns: stats.spread().as_nanos() as u64,
+                input_hash: benchmark.input_hash.clone(),
+            })
+            .collect()
+    }
+
+    /// What the record is a measurement of.
+    pub fn key(&self) -> (u8, &str, Phase, &str) {
+        (self.day, &self.solution, self.phase, &self.input_hash)
+    }
+
+    fn parse(line: &str) -> Result<Record, ParseError> {
+        let mut fields = line.split('\t');
+        let mut field = |expected| {
+            fields
+                .next()
+                .ok_or_else(|| ParseError::missing(line, expected))
+        };
+        let commit = field("a commit")?.to_string();
+        let time = field("a time")?;
+        let day = field("a day")?;
+        let solution = field("a solution")?.to_string();
+        let phase = field("a part")?;
+        let median_ns = field("a median")?;
+        let spread_ns = field("a spread")?;
+        let input_hash = fields.next().unwrap_or_default().to_string();
+
+        Ok(Record {
+            commit,
+            time: parse::value(line, time, "seconds since the epoch")?,
+            day: parse::value(line, day, "a day")?,
+            solution,
+            phase: parse::value(line, phase, "`parse`, `one` or `two`")?,
+            median_ns: parse::value(line, median_ns, "nanoseconds")?,
+            spread_ns: parse::value(line, spread_ns, "nanoseconds")?,
+            input_hash,
+        })
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.commit,
+            self.time,
+            self.day,
+            self.solution,
+            self.phase,
+            self.median_ns,
+            self.spread_ns,
+            self.input_hash
+        )
+    }
+}
+
+/// Where the history is kept unless told otherwise: next to the workspace's
+/// `Cargo.toml`, ignored by git.
+pub fn default_path() -> PathBuf {
+    workspace_dir().join("bench-history.tsv")
+}
+
+/// Every record in the history at `path`, oldest first. A missing file is an
+/// empty history.
+pub fn load(path: &Path) -> Result<Vec<Record>> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e.into()),
+    };
+    let records: Vec<Option<Record>> = parse::lines(&text, |line| {
+        if line.starts_with('#') || line.trim().is_empty() {
+            Ok(None)
+        } else {
+            Record::parse(line).map(Some)
+        }
+    })
+    .map_err(|e| e.in_file(path.display()))?;
+    Ok(records.into_iter().flatten().collect())
+}
+
+/// Adds `records` to the end of the history at `path`, creating it if needed.
+pub fn append(path: &Path, records: &[Record]) -> io::Result<()> {
+    let new = !path.exists();
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    if new {
+        writeln!(file, "{}", HEADER)?;
+    }
+    for record in records {
+        writeln!(file, "{}", record)?;
+    }
+    Ok(())
+}
+
+/// The commit the workspace is at, with `-dirty` appended if it has
+/// uncommitted changes, or `unknown` if git can't tell.
+pub fn current_commit() -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(workspace_dir())
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(commit) => match git(&["status", "--porcelain", "--untracked-files=no"]) {
+            Some(status) if !status.is_empty() => format!("{}-dirty", commit),
+            _ => commit,
+        },
+        None => "unknown".to_string(),
+    }
+}
+
+/// Seconds since the Unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// A solution's latest measurement of a phase, next to its best before that.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change<'a> {
+    pub latest: &'a Record,
+    pub best: &'a Record,
+}
+
+impl Change<'_> {
+    /// How much slower the latest measurement is than the best, in percent.
+    /// Negative if it is faster.
+    pub fn slowdown(&self) -> f64 {
+        (self.latest.median_ns as f64 / self.best.median_ns.max(1) as f64 - 1.0) * 100.0
+    }
+}
+
+/// Compares the latest measurement of everything in `records` to its best
+/// earlier one. Phases only measured once are left out.
+pub fn changes(records: &[Record]) -> Vec<Change<'_>> {
+    let mut history: HashMap<_, Vec<&Record>> = HashMap::new();
+    for record in records {
+        history.entry(record.key()).or_default().push(record);
+    }
+
+    let mut changes: Vec<_> = history
+        .into_values()
+        .filter_map(|mut records| {
+            let latest = records.pop()?;
+            let best = records.into_iter().min_by_key(|record| record.median_ns)?;
+            Some(Change { latest, best })
+        })
+        .collect();
+    changes.sort_by(|a, b| a.latest.key().cmp(&b.latest.key()));
+    changes
+}
+
+/// The changes in `records`, flagging those that got slower than their best
+/// by more than `threshold` percent.
+pub struct Report<'a> {
+    pub changes: Vec<Change<'a>>,
+    pub threshold: f64,
+}
+
+impl<'a> Report<'a> {
+    pub fn new(records: &'a [Record], threshold: f64) -> Report<'a> {
+        Report {
+            changes: changes(records),
+            threshold,
+        }
+    }
+
+    pub fn regressions(&self) -> Vec<&Change<'a>> {
+        self.changes
+            .iter()
+            .filter(|change| change.slowdown() > self.threshold)
+            .collect()
+    }
+}
+
+impl fmt::Display for Report<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<4} {:<28} {:<6} {:<8} {:>10} {:<16} {:>10} {:<16} {:>8}",
+            "day", "solution", "part", "input", "latest", "", "best", "", "change"
+        )?;
+        for change in &self.changes {
+            let (latest, best) = (change.latest, change.best);
+            let flag = if change.slowdown() > self.threshold {
+                "  REGRESSED"
+            } else {
+                ""
+            };
+            writeln!(
+                f,
+                "{:<4} {:<28} {:<6} {:<8} {:>10.1?} {:<16} {:>10.1?} {:<16} {:>+7.1}%{}",
+                latest.day,
+                latest.solution,
+                latest.phase,
+                latest.input_hash.get(..8).unwrap_or("?"),
+                Duration::from_nanos(latest.median_ns),
+                format!("@{}", latest.commit),
+                Duration::from_nanos(best.median_ns),
+                format!("@{}", best.commit),
+                change.slowdown(),
+                flag
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_history() {
+    let record = |commit: &str, phase, median_ns| Record {
+        input_hash: "0123456789abcdef".to_string(),
+        commit: commit.to_string(),
+        time: 0,
+        day: 6,
+        solution: "matt".to_string(),
+        phase,
+        median_ns,
+        spread_ns: 10,
+    };
+    let records = vec![
+        record("a1", Phase::Parse, 1000),
+        record("a1", Phase::Solve(Part::One), 500),
+        record("b2", Phase::Parse, 900),
+        record("b2", Phase::Solve(Part::One), 600),
+        record("c3", Phase::Parse, 950),
+        record("c3", Phase::Solve(Part::Two), 700),
+    ];
+
+    let line = records[1].to_string();
+    assert_eq!("a1\t0\t6\tmatt\tone\t500\t10\t0123456789abcdef", line);
+    assert_eq!(records[1], Record::parse(&line).unwrap());
+    assert!(Record::parse("a1\t0\tsix\tmatt\tone\t500\t10").is_err());
+    // Records from before the input was saved have no hash.
+    let old = Record::parse("a1\t0\t6\tmatt\tone\t500\t10").unwrap();
+    assert_eq!("", old.input_hash);
+
+    // Part two has only been measured once, so there's nothing to compare.
+    let report = Report::new(&records, 10.0);
+    assert_eq!(2, report.changes.len());
+    let regressions = report.regressions();
+    assert_eq!(1, regressions.len());
+    assert_eq!(
+        ("b2", "a1"),
+        (&*regressions[0].latest.commit, &*regressions[0].best.commit)
+    );
+    assert!((regressions[0].slowdown() - 20.0).abs() < 1e-9);
+
+    // A measurement on another input isn't compared with these.
+    let mut records = records;
+    records.push(Record {
+        input_hash: "fedcba9876543210".to_string(),
+        ..record("d4", Phase::Parse, 5000)
+    });
+    let report = Report::new(&records, 10.0);
+    assert_eq!(1, report.regressions().len());
+    assert!(report.to_string().contains("parse  01234567"));
+}
@@ -0,0 +1,19 @@
+//! Everything the `aoc` runner needs to find and drive the solutions of every
+//! day and author.
+pub mod bench;
+pub mod compare;
+pub mod differential;
+pub mod examples;
+pub mod history;
+pub mod reference;
+pub mod registry;
+pub mod scaffold;
+pub mod verified;
+
+use std::path::{Path, PathBuf};
+
+/// The root of the workspace, where every day's crate lives.
+pub fn workspace_dir() -> PathBuf {
+    let aoc = Path::new(env!("CARGO_MANIFEST_DIR"));
+    aoc.parent().unwrap_or(aoc).to_path_buf()
+}
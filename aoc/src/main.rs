@@ -0,0 +1,820 @@
+// Runs any registered solution, e.g. `aoc run --day 4 --author matt --part 2`.
+use std::fs::{self, File};
+use std::io::{self, BufWriter, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::Duration;
+
+use aoc::bench::{Benchmark, HeadToHead};
+use aoc::compare::Comparison;
+use aoc::differential::Property;
+use aoc::examples::{self, Check};
+use aoc::history::{self, Record};
+use aoc::registry;
+use aoc::scaffold::{Dependency, Scaffold};
+use aoc::verified::{self, Verdict, Verified};
+use aoc_common::bench::Config;
+use aoc_common::generate::Rng;
+use aoc_common::input::Source;
+use aoc_common::json::Json;
+use aoc_common::params::Params;
+use aoc_common::report::{Format, Report};
+use aoc_common::{Answer, Part, Solution, Solver};
+use clap::{Args, Parser, Subcommand};
+use day_8::cfg::Cfg;
+use day_8::machine::{Machine, Outcome};
+use day_8::trace::{Diff, Trace};
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2020 solution runner")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve a day's puzzle with one or all of its authors' solutions
+    Run(RunArgs),
+    /// Run every author's solution for a day on one input and compare answers
+    Compare {
+        #[arg(short, long)]
+        day: u8,
+        /// Input file to use instead of the first author's input, or - for
+        /// standard input
+        #[arg(short, long)]
+        input: Option<String>,
+        /// Change one of the puzzle's constants; see `aoc params`
+        #[arg(long = "param", value_name = "NAME=VALUE")]
+        params: Vec<String>,
+    },
+    /// List the puzzle constants each solution lets --param change
+    Params {
+        /// Only list this day's
+        #[arg(short, long)]
+        day: Option<u8>,
+    },
+    /// Check solutions against the worked examples in each day's examples folder
+    Examples {
+        /// Only check this day, instead of every day
+        #[arg(short, long)]
+        day: Option<u8>,
+        /// Only check this author's solutions
+        #[arg(short, long)]
+        author: Option<String>,
+    },
+    /// Generate a random input for a day, along with the answers it should have
+    Generate {
+        #[arg(short, long)]
+        day: u8,
+        /// Seed for the random numbers: the same seed always gives the same
+        /// input
+        #[arg(short, long, default_value_t = 1)]
+        seed: u64,
+        /// Roughly how many lines or records to generate
+        #[arg(long, default_value_t = 200)]
+        size: usize,
+        /// Write the input to <OUTPUT>.txt and the answers to
+        /// <OUTPUT>.answers, instead of the input to standard output and the
+        /// answers to standard error
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Look for random inputs that a day's solutions disagree on
+    Differential {
+        /// Only test this day, instead of every day
+        #[arg(short, long)]
+        day: Option<u8>,
+        /// Seed of the first input; each input after uses the next seed
+        #[arg(short, long, default_value_t = 1)]
+        seed: u64,
+        /// How many inputs to try on each day
+        #[arg(short, long, default_value_t = 100)]
+        cases: u64,
+    },
+    /// Measure how long each phase of a day's solutions takes
+    Bench(BenchArgs),
+    /// Flag solutions that got slower than their best in the benchmark history
+    Report {
+        /// Only report on this day
+        #[arg(short, long)]
+        day: Option<u8>,
+        /// How much slower than its best, in percent, a solution may get
+        #[arg(short, long, default_value_t = 10.0)]
+        threshold: f64,
+        /// History file to read, instead of bench-history.tsv in the workspace
+        #[arg(long)]
+        history: Option<PathBuf>,
+    },
+    /// Step through a day 8 program, reading commands from standard input;
+    /// `help` lists them
+    Debug {
+        /// Program to debug, instead of matt's day 8 input
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+    },
+    /// Record every instruction a day 8 program runs, and how it stops
+    Trace(TraceArgs),
+    /// Find where two traces recorded by `aoc trace` part ways
+    DiffTraces { left: PathBuf, right: PathBuf },
+    /// Analyse a day 8 program's control flow without running it
+    Cfg {
+        /// Program to analyse, instead of matt's day 8 input, or - for
+        /// standard input
+        #[arg(short, long)]
+        input: Option<String>,
+        /// Print the graph for Graphviz instead, with the loop part one runs
+        /// into in red
+        #[arg(long)]
+        dot: bool,
+    },
+    /// List every registered solution
+    List,
+    /// Add a new author's solution to a day, creating the day if need be
+    New {
+        #[arg(short, long)]
+        day: u8,
+        /// Names the module, binary and feature, e.g. my_cool_name
+        #[arg(short, long)]
+        author: String,
+        /// Only build the solution with a feature named after the author
+        #[arg(long)]
+        gated: bool,
+        /// A crate the solution depends on, as <CRATE>=<VERSION>; optional if
+        /// the solution is gated
+        #[arg(long = "dependency", value_name = "CRATE=VERSION")]
+        dependencies: Vec<Dependency>,
+    },
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Only needed to run, not to check
+    #[arg(short, long, required_unless_present = "check")]
+    day: Option<u8>,
+    /// Only run this author's solution
+    #[arg(short, long)]
+    author: Option<String>,
+    /// Only solve this part (1 or 2)
+    #[arg(short, long)]
+    part: Option<Part>,
+    /// Input file to use for every author, or - for standard input
+    #[arg(short, long)]
+    input: Option<String>,
+    /// Only check answers against the verified ones, without timing them;
+    /// checks every day unless given one, skipping missing inputs
+    #[arg(long, conflicts_with = "record")]
+    check: bool,
+    /// Record the answers as verified, once the puzzle site has accepted them
+    #[arg(long)]
+    record: bool,
+    /// Verified answers file to use, instead of verified-answers.tsv in the
+    /// workspace
+    #[arg(long)]
+    verified: Option<PathBuf>,
+    /// Print the answers as text, or one JSON object per part for scripts
+    #[arg(short, long, default_value_t = Format::Text)]
+    format: Format,
+    /// Change one of the puzzle's constants, leaving the answers unchecked;
+    /// see `aoc params`
+    #[arg(
+        long = "param",
+        value_name = "NAME=VALUE",
+        conflicts_with_all = ["check", "record"]
+    )]
+    params: Vec<String>,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    #[arg(short, long)]
+    day: u8,
+    /// Only benchmark this author's solution
+    #[arg(short, long)]
+    author: Option<String>,
+    /// Only benchmark this part (1 or 2), besides parsing
+    #[arg(short, long)]
+    part: Option<Part>,
+    /// Input file to use for every author, or - for standard input
+    #[arg(short, long)]
+    input: Option<String>,
+    /// Also benchmark the authors' alternative implementations
+    #[arg(long)]
+    variants: bool,
+    /// How many samples to take of each phase
+    #[arg(long, default_value_t = 50)]
+    samples: usize,
+    /// How long to warm each phase up for, in milliseconds
+    #[arg(long, default_value_t = 200)]
+    warm_up: u64,
+    /// Add the results to the benchmark history, under the current commit
+    #[arg(long)]
+    save: bool,
+    /// History file to save to, instead of bench-history.tsv in the workspace
+    #[arg(long)]
+    history: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct TraceArgs {
+    /// Program to trace, instead of matt's day 8 input, or - for standard
+    /// input
+    #[arg(short, long)]
+    input: Option<String>,
+    /// Flip the nth `jmp` or `nop` first, counting from 0, as part two's
+    /// brute force does
+    #[arg(long, conflicts_with = "repaired")]
+    flip_nth: Option<usize>,
+    /// Flip the instruction part two finds first
+    #[arg(long)]
+    repaired: bool,
+    /// Write one tab-separated line per step, or one JSON object
+    #[arg(short, long, default_value_t = Format::Text)]
+    format: Format,
+    /// Write the trace here instead of to standard output
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+/// The input named on the command line, read once and shared by every
+/// solution, or else each author's own input.
+struct Inputs(Option<(Source, String)>);
+
+impl Inputs {
+    // Standard input can only be read once, so an explicit input is read up
+    // front.
+    fn read(arg: Option<&str>) -> Result<Inputs, String> {
+        match arg {
+            Some(arg) => {
+                let source = Source::from_arg(arg);
+                let input = source.read().map_err(|e| e.to_string())?;
+                Ok(Inputs(Some((source, input))))
+            }
+            None => Ok(Inputs(None)),
+        }
+    }
+
+    fn get(&self, solution: &Solution) -> Result<(Source, String), String> {
+        match &self.0 {
+            Some((source, input)) => Ok((source.clone(), input.clone())),
+            None => {
+                let source = Source::standard(solution.day, solution.author);
+                let input = source.read().map_err(|e| e.to_string())?;
+                Ok((source, input))
+            }
+        }
+    }
+}
+
+fn find(day: u8, author: Option<&str>) -> Result<Vec<Solution>, String> {
+    let solutions = registry::find(day, author);
+    if solutions.is_empty() {
+        return Err(match author {
+            Some(author) => format!("no solution by {} for day {}", author, day),
+            None => format!("no solutions for day {}", day),
+        });
+    }
+    Ok(solutions)
+}
+
+fn parts(part: Option<Part>) -> Vec<Part> {
+    part.map_or(Part::ALL.to_vec(), |part| vec![part])
+}
+
+fn run_solution(
+    solution: &Solution,
+    (source, input): (Source, String),
+    parts: &[Part],
+    params: &Params,
+    verified: &mut Verified,
+    args: &RunArgs,
+) -> Result<bool, String> {
+    let reports = Report::solve(solution, &input, params, parts)
+        .map_err(|e| e.in_file(&source).to_string())?;
+    if let (Format::Text, false, Some(report)) = (args.format, args.check, reports.first()) {
+        let mut line = format!("    Parse:     [{:?}]", report.parse);
+        if let Some(allocations) = report.parse_allocations {
+            line += &format!("  {}", allocations);
+        }
+        println!("{}", line);
+    }
+
+    let mut matched = true;
+    for report in reports {
+        let (day, part, answer) = (report.day, report.part, &report.answer);
+        let status = if *answer == Answer::Unsolved {
+            Status::Unsolved
+        } else if !params.is_empty() {
+            // The verified answers are for the puzzle's own constants.
+            Status::Unchecked
+        } else if args.record {
+            Status::Recorded(verified.record(day, part, &input, answer))
+        } else {
+            Status::Checked(verified.check(day, part, &input, answer))
+        };
+        matched &= !matches!(status, Status::Checked(Verdict::Mismatch { .. }));
+
+        match args.format {
+            Format::Text => {
+                let mut line = format!("    Part {}:  {}", part, answer);
+                if !args.check {
+                    line += &format!("  [{:?}]", report.solve);
+                    if let Some(allocations) = report.solve_allocations {
+                        line += &format!("  {}", allocations);
+                    }
+                }
+                match status {
+                    Status::Unsolved => {}
+                    Status::Unchecked => line += "  unchecked",
+                    Status::Recorded(None) => line += "  recorded",
+                    Status::Recorded(Some(previous)) => {
+                        line += &format!("  recorded, replacing {}", previous)
+                    }
+                    Status::Checked(verdict) => line += &format!("  {}", verdict),
+                }
+                println!("{}", line);
+            }
+            Format::Json => {
+                let mut json = report.to_json();
+                if let Json::Object(fields) = &mut json {
+                    let mut add = |key: &str, value: Json| fields.push((key.to_string(), value));
+                    match status {
+                        Status::Unsolved => {}
+                        Status::Unchecked => add("verdict", "unchecked".into()),
+                        Status::Recorded(previous) => {
+                            add("verdict", "recorded".into());
+                            if let Some(previous) = previous {
+                                add("replaced", previous.into());
+                            }
+                        }
+                        Status::Checked(Verdict::Mismatch { verified }) => {
+                            add("verdict", "mismatch".into());
+                            add("verified", verified.into());
+                        }
+                        Status::Checked(verdict) => add("verdict", verdict.to_string().into()),
+                    }
+                }
+                println!("{}", json);
+            }
+        }
+    }
+    Ok(matched)
+}
+
+/// Prints a problem with a solution under its heading, or naming it if there
+/// is no heading.
+fn print_error(solution: &Solution, format: Format, message: &str) {
+    match format {
+        Format::Text => eprintln!("    {}", message),
+        Format::Json => eprintln!("Day {} ({}) {}", solution.day, solution.name(), message),
+    }
+}
+
+/// What became of an answer and the verified answers.
+enum Status {
+    Unsolved,
+    /// Solved with parameters changed, so not comparable to a verified answer.
+    Unchecked,
+    /// Recorded as verified, replacing the answer given if there was one.
+    Recorded(Option<String>),
+    Checked(Verdict),
+}
+
+fn run(args: RunArgs) -> Result<(), String> {
+    let author = args.author.as_deref();
+    let solutions = match args.day {
+        Some(day) => find(day, author)?,
+        None => registry::all()
+            .into_iter()
+            .filter(|s| author.is_none_or(|author| author == s.author))
+            .collect(),
+    };
+    let params = Params::from_args(&args.params)?;
+    let inputs = Inputs::read(args.input.as_deref())?;
+    let parts = parts(args.part);
+    let path = args.verified.clone().unwrap_or_else(verified::default_path);
+    let mut verified = Verified::load(&path).map_err(|e| e.to_string())?;
+
+    let (mut failed, mut mismatched) = (false, false);
+    for solution in &solutions {
+        if args.format == Format::Text {
+            println!("Day {} ({})", solution.day, solution.author);
+        }
+        let result = match inputs.get(solution) {
+            // Not every author has solved every day with their own input.
+            Err(e) if args.check && args.input.is_none() => {
+                print_error(solution, args.format, &format!("skipped: {}", e));
+                continue;
+            }
+            input => input.and_then(|input| {
+                run_solution(solution, input, &parts, &params, &mut verified, &args)
+            }),
+        };
+        match result {
+            Ok(matched) => mismatched |= !matched,
+            Err(e) => {
+                print_error(solution, args.format, &format!("error: {}", e));
+                failed = true;
+            }
+        }
+    }
+
+    if args.record {
+        verified
+            .save(&path)
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+    }
+    if mismatched {
+        Err("some answers don't match the verified ones".to_string())
+    } else if failed {
+        Err("some solutions failed".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+fn check_examples(day: Option<u8>, author: Option<&str>) -> Result<(), String> {
+    let mut days: Vec<_> = registry::all().iter().map(|s| s.day).collect();
+    days.dedup();
+    days.retain(|d| day.is_none_or(|day| day == *d));
+    if days.is_empty() {
+        return Err(format!("no solutions for day {}", day.unwrap_or_default()));
+    }
+
+    let mut failed = false;
+    for day in days {
+        let examples = examples::load(day).map_err(|e| e.to_string())?;
+        let mut solutions = find(day, author)?;
+        solutions.extend(registry::find_variants(day, author));
+        for solution in &solutions {
+            println!("Day {} ({})", day, solution.name());
+            for example in &examples {
+                let checks: Vec<_> = example
+                    .check(solution)
+                    .into_iter()
+                    .map(|(part, check)| {
+                        let part = part.to_string().to_lowercase();
+                        match check {
+                            Check::Passed => format!("{}: ok", part),
+                            Check::Unsolved => format!("{}: unsolved", part),
+                            Check::Failed(reason) => {
+                                failed = true;
+                                format!("{}: FAILED ({})", part, reason)
+                            }
+                        }
+                    })
+                    .collect();
+                println!("    {:<10} {}", example.name, checks.join("  "));
+            }
+        }
+    }
+
+    if failed {
+        Err("some examples failed".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+fn generate(day: u8, seed: u64, size: usize, output: Option<&Path>) -> Result<(), String> {
+    let generator = registry::generator(day).ok_or(format!("no generator for day {}", day))?;
+    let generated = generator(&mut Rng::new(seed), size);
+    match output {
+        Some(stem) => {
+            examples::save(stem, &generated).map_err(|e| e.to_string())?;
+            eprintln!(
+                "Saved {}.txt and {}.answers",
+                stem.display(),
+                stem.display()
+            );
+        }
+        None => {
+            print!("{}", generated.input);
+            eprint!("{}", examples::answers_file(&generated));
+        }
+    }
+    Ok(())
+}
+
+fn differential(day: Option<u8>, seed: u64, cases: u64) -> Result<(), String> {
+    let properties: Vec<_> = match day {
+        Some(day) => vec![Property::for_day(day).ok_or(format!("no generator for day {}", day))?],
+        None => (1..=25).filter_map(Property::for_day).collect(),
+    };
+
+    let mut failed = false;
+    for property in properties {
+        match property.check(&property.solutions(), seed, cases) {
+            Some(counterexample) => {
+                print!("{}", counterexample);
+                failed = true;
+            }
+            None => println!("Day {}: agreed on {} inputs", property.day, cases),
+        }
+    }
+
+    if failed {
+        Err("solutions disagree".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+fn bench(args: BenchArgs) -> Result<(), String> {
+    let mut solutions = find(args.day, args.author.as_deref())?;
+    if args.variants {
+        solutions.extend(registry::find_variants(args.day, args.author.as_deref()));
+    }
+    let inputs = Inputs::read(args.input.as_deref())?;
+    let parts = parts(args.part);
+    let config = Config {
+        warm_up: Duration::from_millis(args.warm_up),
+        samples: args.samples,
+        ..Config::default()
+    };
+
+    let mut benchmarks = vec![];
+    let mut failed = false;
+    for solution in &solutions {
+        let benchmark = inputs.get(solution).and_then(|(source, input)| {
+            Benchmark::run(solution, &input, &parts, &config)
+                .map_err(|e| e.in_file(&source).to_string())
+        });
+        match benchmark {
+            Ok(benchmark) => {
+                print!("{}", benchmark);
+                benchmarks.push(benchmark);
+            }
+            Err(e) => {
+                println!("Day {} ({})", solution.day, solution.name());
+                eprintln!("    error: {}", e);
+                failed = true;
+            }
+        }
+    }
+
+    if benchmarks.len() > 1 {
+        println!();
+        print!("{}", HeadToHead(&benchmarks));
+    }
+
+    if args.save {
+        let path = args.history.unwrap_or_else(history::default_path);
+        let (commit, time) = (history::current_commit(), history::now());
+        let records: Vec<_> = benchmarks
+            .iter()
+            .flat_map(|benchmark| Record::from_benchmark(benchmark, &commit, time))
+            .collect();
+        history::append(&path, &records).map_err(|e| format!("{}: {}", path.display(), e))?;
+        println!();
+        println!("Saved to {} as {}", path.display(), commit);
+    }
+
+    if failed {
+        Err("some solutions failed".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+fn report(day: Option<u8>, threshold: f64, path: Option<PathBuf>) -> Result<(), String> {
+    let path = path.unwrap_or_else(history::default_path);
+    let records: Vec<_> = history::load(&path)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|record| day.is_none_or(|day| day == record.day))
+        .collect();
+
+    let report = history::Report::new(&records, threshold);
+    if report.changes.is_empty() {
+        println!("Nothing to compare yet: benchmark with --save at least twice.");
+        return Ok(());
+    }
+    print!("{}", report);
+
+    match report.regressions().len() {
+        0 => Ok(()),
+        n => Err(format!("{} regressed by more than {}%", n, threshold)),
+    }
+}
+
+fn compare(day: u8, input: Option<&str>, params: &[String]) -> Result<(), String> {
+    let params = Params::from_args(params)?;
+    let solutions = find(day, None)?;
+    let source = match (input, solutions.first()) {
+        (Some(arg), _) => Source::from_arg(arg),
+        (None, _) => Source::standard(day, solutions[0].author),
+    };
+    let input = source.read().map_err(|e| e.to_string())?;
+
+    println!("Day {} on {}", day, source);
+    let comparison = Comparison::run(&solutions, &input, &params);
+    print!("{}", comparison);
+
+    if !comparison.disagreements().is_empty() {
+        Err("solutions disagree".to_string())
+    } else if comparison.has_errors() {
+        Err("some solutions failed".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+fn debug(input: Option<PathBuf>) -> Result<(), String> {
+    // Standard input carries the commands, so the program has to be in a file.
+    let source = match input {
+        Some(path) if path == Path::new("-") => {
+            return Err("the program must be in a file; commands come from standard input".into())
+        }
+        Some(path) => Source::File(path),
+        None => Source::standard(8, "matt"),
+    };
+    let input = source.read().map_err(|e| e.to_string())?;
+    let program = day_8::matt::Matt::parse(&input).map_err(|e| e.to_string())?;
+
+    let stdin = io::stdin();
+    let prompt = if stdin.is_terminal() {
+        println!("Debugging {}; `help` lists the commands", source);
+        Some("(aoc) ")
+    } else {
+        None
+    };
+    day_8::debugger::Debugger::new(&program)
+        .run(stdin.lock(), io::stdout().lock(), prompt)
+        .map_err(|e| e.to_string())
+}
+
+fn trace(args: TraceArgs) -> Result<(), String> {
+    let source = Source::locate(8, "matt", args.input.as_deref());
+    let input = source.read().map_err(|e| e.to_string())?;
+    let mut program = day_8::matt::Matt::parse(&input).map_err(|e| e.to_string())?;
+
+    let original = program.clone();
+    let flipped = match (args.flip_nth, args.repaired) {
+        (Some(nth), _) => Some(day_8::matt::flip_nth_instruction(&mut program, nth).ok_or(
+            format!(
+                "there aren't {} `jmp`s or `nop`s to flip before the last instruction",
+                nth + 1
+            ),
+        )?),
+        (None, true) => {
+            let repair =
+                day_8::repair::repair(&program).ok_or("no one flip makes the program terminate")?;
+            program[repair.flipped] = day_8::repair::flip(program[repair.flipped]).unwrap();
+            Some(repair.flipped)
+        }
+        (None, false) => None,
+    };
+    if let Some(pc) = flipped {
+        eprintln!("Flipped {}: {} -> {}", pc, original[pc], program[pc]);
+    }
+
+    let trace = Trace::record(&mut Machine::new(&program));
+    match args.output {
+        Some(path) => {
+            let file = File::create(&path)
+                .map_err(|e| format!("unable to write {}: {}", path.display(), e))?;
+            trace
+                .write(BufWriter::new(file), args.format)
+                .map_err(|e| e.to_string())?;
+            eprintln!(
+                "Saved the trace of {} steps to {}",
+                trace.steps.len(),
+                path.display()
+            );
+        }
+        None => trace
+            .write(io::stdout().lock(), args.format)
+            .map_err(|e| e.to_string())?,
+    }
+    Ok(())
+}
+
+fn diff_traces(left: &Path, right: &Path) -> Result<(), String> {
+    let read = |path: &Path| {
+        let trace = fs::read_to_string(path)
+            .map_err(|e| format!("unable to read {}: {}", path.display(), e))?;
+        Trace::parse(&trace).map_err(|e| e.in_file(path.display()).to_string())
+    };
+    let diff = Diff::new(&read(left)?, &read(right)?).to_string();
+    println!("left   {}\nright  {}", left.display(), right.display());
+    print!("{}", diff);
+    Ok(())
+}
+
+fn cfg(input: Option<&str>, dot: bool) -> Result<(), String> {
+    let source = Source::locate(8, "matt", input);
+    let input = source.read().map_err(|e| e.to_string())?;
+    let program = day_8::matt::Matt::parse(&input).map_err(|e| e.to_string())?;
+
+    let cfg = Cfg::new(&program);
+    if dot {
+        let highlight = match day_8::matt::part_one(&program) {
+            Outcome::InfiniteLoop { pc, .. } => cfg.loop_through(pc).unwrap_or_default(),
+            _ => vec![],
+        };
+        print!("{}", cfg.dot(&highlight));
+    } else {
+        println!("Day 8 program at {}", source);
+        print!("{}", cfg);
+    }
+    Ok(())
+}
+
+fn list() -> Result<(), String> {
+    for solution in registry::all() {
+        println!(
+            "day {}\t{}\t{}",
+            solution.day,
+            solution.author,
+            Source::standard(solution.day, solution.author)
+        );
+    }
+    Ok(())
+}
+
+fn params(day: Option<u8>) -> Result<(), String> {
+    let mut solutions = registry::all();
+    solutions.extend(registry::variants());
+    solutions.retain(|s| day.is_none_or(|day| day == s.day));
+    solutions.sort_by_key(|s| s.day);
+    if solutions.is_empty() {
+        return Err(format!("no solutions for day {}", day.unwrap_or_default()));
+    }
+
+    for solution in solutions {
+        println!("Day {} ({})", solution.day, solution.name());
+        if solution.parameters().is_empty() {
+            println!("    no parameters");
+        }
+        for parameter in solution.parameters() {
+            println!(
+                "    {:<8} {:<22} {}",
+                parameter.name,
+                format!("[{}]", parameter.default),
+                parameter.about
+            );
+        }
+    }
+    Ok(())
+}
+
+fn new(scaffold: Scaffold) -> Result<(), String> {
+    let changes = scaffold
+        .apply(&aoc::workspace_dir())
+        .map_err(|e| e.to_string())?;
+    if changes.is_empty() {
+        println!(
+            "Day {} already has {}'s solution; nothing to do",
+            scaffold.day, scaffold.author
+        );
+    }
+    for change in changes {
+        println!("{}", change);
+    }
+    Ok(())
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Command::Run(args) => run(args),
+        Command::Compare { day, input, params } => compare(day, input.as_deref(), &params),
+        Command::Params { day } => params(day),
+        Command::Examples { day, author } => check_examples(day, author.as_deref()),
+        Command::Generate {
+            day,
+            seed,
+            size,
+            output,
+        } => generate(day, seed, size, output.as_deref()),
+        Command::Differential { day, seed, cases } => differential(day, seed, cases),
+        Command::Bench(args) => bench(args),
+        Command::Report {
+            day,
+            threshold,
+            history,
+        } => report(day, threshold, history),
+        Command::Debug { input } => debug(input),
+        Command::Trace(args) => trace(args),
+        Command::DiffTraces { left, right } => diff_traces(&left, &right),
+        Command::Cfg { input, dot } => cfg(input.as_deref(), dot),
+        Command::List => list(),
+        Command::New {
+            day,
+            author,
+            gated,
+            dependencies,
+        } => new(Scaffold {
+            day,
+            author,
+            gated,
+            dependencies,
+        }),
+    };
+    if let Err(e) = result {
+        eprintln!("aoc: {}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,212 @@
+// Plain models of the puzzles only one author has solved, written for
+// clarity rather than speed, to test that author's solution against.
+use std::collections::HashMap;
+
+use aoc_common::{parse, Answer, ParseError, Result, Solver};
+
+/// The bag rules of day 7: what each bag must directly hold.
+pub struct Bags;
+
+pub type Rules = HashMap<String, Vec<(u64, String)>>;
+
+impl Solver for Bags {
+    type Input = Rules;
+
+    fn parse(input: &str) -> Result<Rules> {
+        Ok(parse::lines(input, |line| {
+            let (bag, contents) = parse::split_once(
+                line,
+                line,
+                " bags contain ",
+                "`<color> bags contain <bags>`",
+            )?;
+            let contents = contents.trim_end_matches('.');
+            if contents == "no other bags" {
+                return Ok((bag.to_string(), vec![]));
+            }
+            let held = contents
+                .split(", ")
+                .map(|held| {
+                    let (number, bag) = parse::split_once(line, held, " ", "`<count> <color>`")?;
+                    let bag = bag.trim_end_matches(" bags").trim_end_matches(" bag");
+                    Ok((parse::value(line, number, "a number")?, bag.to_string()))
+                })
+                .collect::<Result<_, ParseError>>()?;
+            Ok((bag.to_string(), held))
+        })?)
+    }
+
+    fn part_one(rules: &Rules) -> Answer {
+        rules
+            .keys()
+            .filter(|bag| holds(rules, bag, "shiny gold"))
+            .count()
+            .into()
+    }
+
+    fn part_two(rules: &Rules) -> Answer {
+        inside(rules, "shiny gold").into()
+    }
+}
+
+/// Whether `bag` ends up holding `wanted`, however deep down.
+fn holds(rules: &Rules, bag: &str, wanted: &str) -> bool {
+    rules[bag]
+        .iter()
+        .any(|(_, held)| held == wanted || holds(rules, held, wanted))
+}
+
+/// How many bags `bag` ends up holding.
+fn inside(rules: &Rules, bag: &str) -> u64 {
+    rules[bag]
+        .iter()
+        .map(|(number, held)| number * (1 + inside(rules, held)))
+        .sum()
+}
+
+/// Whether the rules are a puzzle input: every bag mentioned has a rule, shiny
+/// gold among them, and no bag ends up inside itself.
+pub fn valid_bags(rules: &Rules) -> bool {
+    fn acyclic<'a>(rules: &'a Rules, bag: &'a str, path: &mut Vec<&'a str>) -> bool {
+        if path.contains(&bag) {
+            return false;
+        }
+        path.push(bag);
+        let ok = rules[bag]
+            .iter()
+            .all(|(_, held)| acyclic(rules, held, path));
+        path.pop();
+        ok
+    }
+
+    rules.contains_key("shiny gold")
+        && rules
+            .values()
+            .flatten()
+            .all(|(_, held)| rules.contains_key(held))
+        && rules.keys().all(|bag| acyclic(rules, bag, &mut vec![]))
+}
+
+/// The boot code of day 8.
+pub struct Handheld;
+
+pub type Program = Vec<(String, i64)>;
+
+/// How running a program ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exit {
+    /// It was about to run an instruction for the second time.
+    Looped(i64),
+    /// It ran off the end of the program.
+    Terminated(i64),
+    /// It jumped somewhere else outside the program.
+    OutOfBounds,
+}
+
+impl Solver for Handheld {
+    type Input = Program;
+
+    fn parse(input: &str) -> Result<Program> {
+        Ok(parse::lines(input, |line| {
+            let (operation, argument) = parse::split_once(line, line, " ", "`<op> <arg>`")?;
+            if !["acc", "jmp", "nop"].contains(&operation) {
+                return Err(ParseError::at(line, operation, "`acc`, `jmp` or `nop`"));
+            }
+            let argument = argument.strip_prefix('+').unwrap_or(argument);
+            Ok((
+                operation.to_string(),
+                parse::value(line, argument, "a number")?,
+            ))
+        })?)
+    }
+
+    fn part_one(program: &Program) -> Answer {
+        match run(program) {
+            Exit::Looped(acc) => acc.into(),
+            exit => panic!("program didn't loop: {:?}", exit),
+        }
+    }
+
+    fn part_two(program: &Program) -> Answer {
+        match fixes(program).as_slice() {
+            [acc] => (*acc).into(),
+            fixes => panic!("{} ways to fix the program", fixes.len()),
+        }
+    }
+}
+
+pub fn run(program: &[(String, i64)]) -> Exit {
+    let (mut acc, mut pc) = (0, 0i64);
+    let mut seen = vec![false; program.len()];
+    loop {
+        if pc == program.len() as i64 {
+            return Exit::Terminated(acc);
+        }
+        if pc < 0 || pc > program.len() as i64 {
+            return Exit::OutOfBounds;
+        }
+        if seen[pc as usize] {
+            return Exit::Looped(acc);
+        }
+        seen[pc as usize] = true;
+        let (operation, argument) = &program[pc as usize];
+        match operation.as_str() {
+            "acc" => acc += argument,
+            "jmp" => pc += argument - 1,
+            _ => {}
+        }
+        pc += 1;
+    }
+}
+
+/// The accumulator at the end of every program that terminates once one of
+/// its `jmp`s is made a `nop` or the other way around.
+pub fn fixes(program: &Program) -> Vec<i64> {
+    (0..program.len())
+        .filter_map(|i| {
+            let flipped = match program[i].0.as_str() {
+                "jmp" => "nop",
+                "nop" => "jmp",
+                _ => return None,
+            };
+            let mut fixed = program.clone();
+            fixed[i].0 = flipped.to_string();
+            match run(&fixed) {
+                Exit::Terminated(acc) => Some(acc),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn test_reference() {
+    let bags = "\
+light red bags contain 1 bright white bag, 2 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.
+";
+    let rules = Bags::parse(bags).unwrap();
+    assert!(valid_bags(&rules));
+    assert_eq!(
+        (Answer::Int(3), Answer::Int(32)),
+        (Bags::part_one(&rules), Bags::part_two(&rules))
+    );
+    let cycle = Bags::parse(
+        "shiny gold bags contain 1 dull red bag.\ndull red bags contain 2 shiny gold bags.",
+    )
+    .unwrap();
+    assert!(!valid_bags(&cycle));
+
+    let program = Handheld::parse(
+        "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n",
+    )
+    .unwrap();
+    assert_eq!(Exit::Looped(5), run(&program));
+    assert_eq!(vec![8], fixes(&program));
+}
@@ -0,0 +1,91 @@
+// Every author's solution for every day, so tooling can look them up by day
+// and author instead of by binary name.
+use aoc_common::generate::Generator;
+use aoc_common::Solution;
+
+/// Every registered solution, ordered by day.
+pub fn all() -> Vec<Solution> {
+    let days = [
+        day_1::solutions,
+        day_2::solutions,
+        day_3::solutions,
+        day_4::solutions,
+        day_5::solutions,
+        day_6::solutions,
+        day_7::solutions,
+        day_8::solutions,
+    ];
+    days.iter().flat_map(|solutions| solutions()).collect()
+}
+
+/// Alternative implementations that authors keep around to benchmark against
+/// their registered solution, ordered by day.
+pub fn variants() -> Vec<Solution> {
+    let days = [day_3::variants, day_8::variants];
+    days.iter().flat_map(|variants| variants()).collect()
+}
+
+/// The generator of random inputs for `day`, if there is one.
+pub fn generator(day: u8) -> Option<Generator> {
+    let generator: Generator = match day {
+        1 => day_1::generate::input,
+        2 => day_2::generate::input,
+        3 => day_3::generate::input,
+        4 => day_4::generate::input,
+        5 => day_5::generate::input,
+        6 => day_6::generate::input,
+        7 => day_7::generate::input,
+        8 => day_8::generate::input,
+        _ => return None,
+    };
+    Some(generator)
+}
+
+/// The solutions for `day`, or just the one by `author` if given.
+pub fn find(day: u8, author: Option<&str>) -> Vec<Solution> {
+    filter(all(), day, author)
+}
+
+/// The variants for `day`, or just the ones by `author` if given.
+pub fn find_variants(day: u8, author: Option<&str>) -> Vec<Solution> {
+    filter(variants(), day, author)
+}
+
+fn filter(solutions: Vec<Solution>, day: u8, author: Option<&str>) -> Vec<Solution> {
+    solutions
+        .into_iter()
+        .filter(|s| s.day == day && author.is_none_or(|author| author == s.author))
+        .collect()
+}
+
+#[test]
+fn test_registry() {
+    let solutions = all();
+    for day in 1..=8 {
+        assert!(solutions.iter().any(|s| s.day == day && s.author == "matt"));
+    }
+
+    let mut keys: Vec<_> = solutions.iter().map(|s| (s.day, s.author)).collect();
+    keys.dedup();
+    assert_eq!(solutions.len(), keys.len());
+}
+
+#[test]
+fn test_generators() {
+    // Days scaffolded since have no generator until someone writes one.
+    for day in 1..=8 {
+        assert!(generator(day).is_some());
+    }
+    assert!(generator(26).is_none());
+}
+
+#[test]
+fn test_variants() {
+    let solutions = all();
+    for variant in variants() {
+        assert!(variant.variant.is_some());
+        assert!(solutions
+            .iter()
+            .any(|s| s.day == variant.day && s.author == variant.author));
+    }
+}
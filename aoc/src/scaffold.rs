@@ -0,0 +1,628 @@
+// Scaffolding for a new author's solution to a day, and for the day's crate if
+// nobody has solved it yet: the author's module and binary, empty examples,
+// and every registration that ties them into the workspace and the runner.
+//
+// Each step first checks whether it's been done already, so scaffolding the
+// same solution twice changes nothing the second time. Manifests and sources
+// are edited as text, to leave their layout and comments as they are.
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// A solution to scaffold.
+#[derive(Debug, Clone)]
+pub struct Scaffold {
+    pub day: u8,
+    /// Becomes the module, binary and feature name, so must be a valid Rust
+    /// identifier in lower case, e.g. `vickz84259`.
+    pub author: String,
+    /// Whether to put the solution behind a feature named after the author.
+    pub gated: bool,
+    /// Dependencies of the solution, optional if it's gated.
+    pub dependencies: Vec<Dependency>,
+}
+
+/// A crate from crates.io, e.g. `itertools=^0.9`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dependency {
+    pub name: String,
+    pub version: String,
+}
+
+impl FromStr for Dependency {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Dependency, Self::Err> {
+        match s.split_once('=') {
+            Some((name, version)) if !name.is_empty() && !version.is_empty() => Ok(Dependency {
+                name: name.to_string(),
+                version: version.to_string(),
+            }),
+            _ => Err(format!("expected `<crate>=<version>`, got `{}`", s)),
+        }
+    }
+}
+
+/// A file the scaffolding wrote, relative to the workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    Created(PathBuf),
+    Updated(PathBuf),
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::Created(path) => write!(f, "created {}", path.display()),
+            Change::Updated(path) => write!(f, "updated {}", path.display()),
+        }
+    }
+}
+
+impl Scaffold {
+    /// Adds whatever's missing of the solution to the workspace at `root`,
+    /// returning the files that were written.
+    pub fn apply(&self, root: &Path) -> io::Result<Vec<Change>> {
+        if !is_identifier(&self.author) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "`{}` can't name a module: use lower case letters, digits and underscores",
+                    self.author
+                ),
+            ));
+        }
+        if self.day == 0 || self.day > 25 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("there's no day {} in Advent of Code", self.day),
+            ));
+        }
+
+        let mut files = Files {
+            root,
+            changes: vec![],
+        };
+        let crate_dir = PathBuf::from(format!("day-{}", self.day));
+        let src = crate_dir.join("src");
+
+        files.create(&crate_dir.join("Cargo.toml"), &self.manifest())?;
+        files.update(&crate_dir.join("Cargo.toml"), |text| {
+            self.register_binary(text)
+        })?;
+        files.create(&src.join("lib.rs"), &self.library())?;
+        files.update(&src.join("lib.rs"), |text| self.register_module(text))?;
+        files.create(&src.join(format!("{}.rs", self.author)), &self.module())?;
+        files.create(
+            &src.join("bin").join(format!("{}.rs", self.author)),
+            &self.binary(),
+        )?;
+        let examples = crate_dir.join("examples");
+        files.create(&examples.join("example.txt"), "")?;
+        files.create(&examples.join("example.answers"), "")?;
+
+        files.update(Path::new("Cargo.toml"), |text| {
+            add_to_list(text, "members", &format!("day-{}", self.day))
+        })?;
+        files.update(Path::new("aoc/Cargo.toml"), |text| {
+            self.register_with_runner(text)
+        })?;
+        files.update(Path::new("aoc/src/registry.rs"), |text| {
+            register_day(text, self.day)
+        })?;
+        Ok(files.changes)
+    }
+
+    fn manifest(&self) -> String {
+        format!(
+            r#"[package]
+name = "day-{}"
+version = "0.1.0"
+authors = [""]
+edition = "2018"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+aoc-common = {{ path = "../aoc-common" }}
+"#,
+            self.day
+        )
+    }
+
+    /// Adds the author's dependencies, feature and binary to a day's manifest.
+    fn register_binary(&self, manifest: &str) -> String {
+        let mut manifest = manifest.to_string();
+        for dependency in &self.dependencies {
+            let line = if self.gated {
+                format!(
+                    "{} = {{ version = \"{}\", optional = true }}",
+                    dependency.name, dependency.version
+                )
+            } else {
+                format!("{} = \"{}\"", dependency.name, dependency.version)
+            };
+            manifest = add_to_section(&manifest, "[dependencies]", &dependency.name, &line);
+        }
+
+        if self.gated {
+            let dependencies: Vec<_> = self
+                .dependencies
+                .iter()
+                .map(|d| format!("{:?}", d.name))
+                .collect();
+            let line = format!("{} = [{}]", self.author, dependencies.join(", "));
+            manifest = add_to_section(&manifest, "[features]", &self.author, &line);
+            for dependency in &self.dependencies {
+                manifest = add_to_list(&manifest, &self.author, &dependency.name);
+            }
+        }
+
+        let name = format!("name = \"day-{}-{}\"", self.day, self.author);
+        if !manifest.lines().any(|line| line.trim() == name) {
+            if !manifest.ends_with('\n') {
+                manifest.push('\n');
+            }
+            manifest += &format!(
+                "\n[[bin]]\n{}\npath = \"src/bin/{}.rs\"\n",
+                name, self.author
+            );
+            if self.gated {
+                manifest += &format!("required-features = [\"{}\"]\n", self.author);
+            }
+        }
+        manifest
+    }
+
+    /// Adds the author's day to the runner's dependencies and, if the solution
+    /// is gated, its feature to the runner's feature of the same name.
+    fn register_with_runner(&self, manifest: &str) -> String {
+        let day = format!("day-{}", self.day);
+        let line = format!("{} = {{ path = \"../{}\" }}", day, day);
+        let mut manifest = add_to_section(manifest, "[dependencies]", &day, &line);
+        if self.gated {
+            let line = format!("{} = []", self.author);
+            manifest = add_to_section(&manifest, "[features]", &self.author, &line);
+            let feature = format!("{}/{}", day, self.author);
+            manifest = add_to_list(&manifest, &self.author, &feature);
+            manifest = add_to_list(&manifest, "default", &self.author);
+        }
+        manifest
+    }
+
+    fn library(&self) -> String {
+        format!(
+            "// --- Day {day} ---
+//
+// https://adventofcode.com/2020/day/{day}
+use aoc_common::Solution;
+
+/// Every author's solution for the day.
+pub fn solutions() -> Vec<Solution> {{
+    vec![]
+}}
+",
+            day = self.day
+        )
+    }
+
+    /// Declares the author's module in a day's library, and adds the author's
+    /// solver to its solutions.
+    fn register_module(&self, library: &str) -> String {
+        let cfg = format!("#[cfg(feature = \"{}\")]", self.author);
+        let mut library = library.to_string();
+
+        let module = format!("pub mod {};", self.author);
+        if !library.lines().any(|line| line.trim() == module) {
+            let lines: Vec<_> = library.lines().collect();
+            let (at, blank) = match lines.iter().rposition(|l| l.starts_with("pub mod ")) {
+                Some(i) => (i + 1, false),
+                None => match lines.iter().rposition(|l| l.starts_with("use ")) {
+                    Some(i) => (i + 1, true),
+                    None => (0, true),
+                },
+            };
+            let mut declaration = vec![];
+            if blank {
+                declaration.push("");
+            }
+            if self.gated {
+                declaration.push(&cfg);
+            }
+            declaration.push(&module);
+            library = splice(&lines, at, &declaration);
+        }
+
+        let solver = format!(
+            "Solution::new::<{}::{}>({}, \"{}\")",
+            self.author,
+            type_name(&self.author),
+            self.day,
+            self.author
+        );
+        if library.contains(&solver) {
+            return library;
+        }
+        let start = match library.find("pub fn solutions()") {
+            Some(function) => match library[function..].find("vec![") {
+                Some(vec) => function + vec + "vec![".len(),
+                None => return library,
+            },
+            None => return library,
+        };
+        let end = match closing_bracket(&library[start..]) {
+            Some(end) => start + end,
+            None => return library,
+        };
+
+        let body = &library[start..end];
+        let entry = if self.gated {
+            format!("        {}\n        {},\n", cfg, solver)
+        } else {
+            format!("        {},\n", solver)
+        };
+        if body.contains('\n') {
+            // One solution to a line, with the closing bracket on its own.
+            let line_start = library[..end].rfind('\n').map_or(0, |i| i + 1);
+            library.insert_str(line_start, &entry);
+        } else if body.trim().is_empty() && !self.gated {
+            library.replace_range(start..end, &solver);
+        } else {
+            let mut lines = String::from("\n");
+            let existing = body.trim().trim_end_matches(',');
+            if !existing.is_empty() {
+                lines += &format!("        {},\n", existing);
+            }
+            lines += &entry;
+            lines += "    ";
+            library.replace_range(start..end, &lines);
+        }
+        library
+    }
+
+    fn module(&self) -> String {
+        format!(
+            "// --- Day {day} ---
+//
+// https://adventofcode.com/2020/day/{day}
+use aoc_common::{{parse, Answer, Result, Solver}};
+
+/// {author}'s solution.
+pub struct {name};
+
+impl Solver for {name} {{
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input> {{
+        Ok(parse::lines(input, |line| Ok(line.to_string()))?)
+    }}
+
+    fn part_one(_input: &Self::Input) -> Answer {{
+        Answer::Unsolved
+    }}
+
+    fn part_two(_input: &Self::Input) -> Answer {{
+        Answer::Unsolved
+    }}
+}}
+
+/// Prints the answers for `input`.
+pub fn main(input: &str) -> Result<()> {{
+    let input = {name}::parse(input)?;
+
+    println!(\"Part One: {{}}\", {name}::part_one(&input));
+    println!(\"Part Two: {{}}\", {name}::part_two(&input));
+    Ok(())
+}}
+",
+            day = self.day,
+            author = self.author,
+            name = type_name(&self.author)
+        )
+    }
+
+    fn binary(&self) -> String {
+        let solution = format!(
+            "Solution::new::<{}>({}, \"{}\")",
+            type_name(&self.author),
+            self.day,
+            self.author
+        );
+        let main = format!("{}::main", self.author);
+        let mut call = format!(
+            "    aoc_common::input::run_with_input({}, {})",
+            solution, main
+        );
+        // As rustfmt would have it.
+        if call.len() > 100 {
+            call = format!(
+                "    aoc_common::input::run_with_input(\n        {},\n        {},\n    )",
+                solution, main
+            );
+        }
+        format!(
+            "use aoc_common::Solution;\nuse day_{}::{}::{{self, {}}};\n\nfn main() {{\n{}\n}}\n",
+            self.day,
+            self.author,
+            type_name(&self.author),
+            call
+        )
+    }
+}
+
+/// Writes files under the workspace, keeping track of what changed.
+struct Files<'a> {
+    root: &'a Path,
+    changes: Vec<Change>,
+}
+
+impl Files<'_> {
+    /// Writes `contents` to `path`, unless there's a file there already.
+    fn create(&mut self, path: &Path, contents: &str) -> io::Result<()> {
+        let full = self.root.join(path);
+        if full.exists() {
+            return Ok(());
+        }
+        if let Some(dir) = full.parent() {
+            fs::create_dir_all(dir).map_err(naming(dir))?;
+        }
+        fs::write(&full, contents).map_err(naming(&full))?;
+        self.changes.push(Change::Created(path.to_path_buf()));
+        Ok(())
+    }
+
+    /// Rewrites the file at `path` with `edit`, if that changes it.
+    fn update<F: FnOnce(&str) -> String>(&mut self, path: &Path, edit: F) -> io::Result<()> {
+        let full = self.root.join(path);
+        let text = fs::read_to_string(&full).map_err(naming(&full))?;
+        let edited = edit(&text);
+        if edited == text {
+            return Ok(());
+        }
+        fs::write(&full, edited).map_err(naming(&full))?;
+        let path = path.to_path_buf();
+        if !self.changes.contains(&Change::Created(path.clone())) {
+            self.changes.push(Change::Updated(path));
+        }
+        Ok(())
+    }
+}
+
+/// Adds `path` to the message of an error about it.
+fn naming(path: &Path) -> impl FnOnce(io::Error) -> io::Error + '_ {
+    move |e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e))
+}
+
+fn is_identifier(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// The name of an author's solver: `my_cool_name` becomes `MyCoolName`.
+fn type_name(author: &str) -> String {
+    author
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or(String::new(), |first| {
+                first.to_ascii_uppercase().to_string() + chars.as_str()
+            })
+        })
+        .collect()
+}
+
+/// `lines`, with `inserted` added before line `at`.
+fn splice(lines: &[&str], at: usize, inserted: &[&str]) -> String {
+    lines[..at]
+        .iter()
+        .chain(inserted)
+        .chain(&lines[at..])
+        .map(|line| format!("{}\n", line))
+        .collect()
+}
+
+/// Where the bracket closing the one just before `text` is.
+fn closing_bracket(text: &str) -> Option<usize> {
+    let mut depth = 1;
+    for (i, c) in text.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            _ => continue,
+        }
+        if depth == 0 {
+            return Some(i);
+        }
+    }
+    None
+}
+
+/// Adds `line` to a section of a manifest, unless the section already sets
+/// `key`. A missing section is added before the first binary.
+fn add_to_section(manifest: &str, section: &str, key: &str, line: &str) -> String {
+    let lines: Vec<_> = manifest.lines().collect();
+    let sets = |l: &&str| {
+        l.strip_prefix(key)
+            .is_some_and(|rest| rest.trim_start().starts_with('='))
+    };
+    let header = match lines.iter().position(|l| l.trim() == section) {
+        Some(header) => header,
+        None => {
+            let at = lines
+                .iter()
+                .position(|l| l.trim() == "[[bin]]")
+                .unwrap_or(lines.len());
+            let mut added = vec![section, line, ""];
+            if at == lines.len() {
+                added.rotate_right(1);
+            }
+            return splice(&lines, at, &added);
+        }
+    };
+    let end = lines[header + 1..]
+        .iter()
+        .position(|l| l.starts_with('['))
+        .map_or(lines.len(), |i| header + 1 + i);
+    if lines[header + 1..end].iter().any(sets) {
+        return manifest.to_string();
+    }
+    let last = lines[..end]
+        .iter()
+        .rposition(|l| !l.trim().is_empty())
+        .unwrap_or(header);
+    splice(&lines, last + 1, &[line])
+}
+
+/// Adds `item` to the list of strings that `key` is set to, unless it's there
+/// already or nothing sets `key`.
+fn add_to_list(manifest: &str, key: &str, item: &str) -> String {
+    let quoted = format!("{:?}", item);
+    let start = match manifest.lines().find(|line| {
+        line.strip_prefix(key)
+            .is_some_and(|rest| rest.trim_start().starts_with("= ["))
+    }) {
+        Some(line) => {
+            let line_start = line.as_ptr() as usize - manifest.as_ptr() as usize;
+            line_start + line.find('[').unwrap_or(0) + 1
+        }
+        None => return manifest.to_string(),
+    };
+    let end = match closing_bracket(&manifest[start..]) {
+        Some(end) => start + end,
+        None => return manifest.to_string(),
+    };
+
+    let body = &manifest[start..end];
+    if body.contains(&quoted) {
+        return manifest.to_string();
+    }
+    let mut manifest = manifest.to_string();
+    if body.contains('\n') {
+        let line_start = manifest[..end].rfind('\n').map_or(0, |i| i + 1);
+        manifest.insert_str(line_start, &format!("    {},\n", quoted));
+    } else if body.trim().is_empty() {
+        manifest.replace_range(start..end, &quoted);
+    } else {
+        manifest.insert_str(end, &format!(", {}", quoted));
+    }
+    manifest
+}
+
+/// Adds a day's solutions to every solution in the registry, after the days
+/// before it.
+fn register_day(registry: &str, day: u8) -> String {
+    let entry = format!("day_{}::solutions,", day);
+    let lines: Vec<_> = registry.lines().collect();
+    if lines.iter().any(|line| line.trim() == entry) {
+        return registry.to_string();
+    }
+    let earlier = |line: &&str| {
+        line.trim()
+            .strip_prefix("day_")
+            .and_then(|rest| rest.strip_suffix("::solutions,"))
+            .and_then(|d| d.parse::<u8>().ok())
+            .is_some_and(|d| d < day)
+    };
+    match lines.iter().rposition(earlier) {
+        Some(i) => {
+            let indent = &lines[i][..lines[i].len() - lines[i].trim_start().len()];
+            splice(&lines, i + 1, &[&format!("{}{}", indent, entry)])
+        }
+        None => registry.to_string(),
+    }
+}
+
+#[test]
+fn test_scaffold() {
+    let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+    let workspace = crate::workspace_dir();
+    for file in [
+        "Cargo.toml",
+        "aoc/Cargo.toml",
+        "aoc/src/registry.rs",
+        "day-7/Cargo.toml",
+        "day-7/src/lib.rs",
+    ] {
+        fs::create_dir_all(root.join(file).parent().unwrap()).unwrap();
+        fs::copy(workspace.join(file), root.join(file)).unwrap();
+    }
+    let read = |file: &str| fs::read_to_string(root.join(file)).unwrap();
+
+    // A day nobody has solved yet.
+    let ada = Scaffold {
+        day: 9,
+        author: "ada".to_string(),
+        gated: false,
+        dependencies: vec![],
+    };
+    let changes = ada.apply(&root).unwrap();
+    assert_eq!(9, changes.len());
+    assert!(changes.contains(&Change::Created("day-9/src/bin/ada.rs".into())));
+    assert!(changes.contains(&Change::Updated("aoc/src/registry.rs".into())));
+    assert!(read("day-9/src/lib.rs").contains(
+        "pub mod ada;\n\n/// Every author's solution for the day.\npub fn solutions() -> \
+         Vec<Solution> {\n    vec![Solution::new::<ada::Ada>(9, \"ada\")]\n}"
+    ));
+    assert!(read("Cargo.toml").contains("    \"day-8\",\n    \"day-9\",\n]"));
+    assert!(read("aoc/Cargo.toml").contains("day-9 = { path = \"../day-9\" }\n\n\n[features]"));
+    assert!(read("aoc/src/registry.rs").contains("day_8::solutions,\n        day_9::solutions,\n"));
+    assert!(ada.apply(&root).unwrap().is_empty());
+
+    // A gated solution with a dependency, to a day that already has one.
+    let grace = Scaffold {
+        day: 7,
+        author: "grace_hopper".to_string(),
+        gated: true,
+        dependencies: vec!["itertools=^0.9".parse().unwrap()],
+    };
+    let changes = grace.apply(&root).unwrap();
+    assert_eq!(7, changes.len());
+    assert!(read("day-7/src/lib.rs")
+        .contains("pub mod matt;\n#[cfg(feature = \"grace_hopper\")]\npub mod grace_hopper;\n"));
+    assert!(read("day-7/src/lib.rs").contains(
+        "    vec![\n        Solution::new::<matt::Matt>(7, \"matt\"),\n        \
+         #[cfg(feature = \"grace_hopper\")]\n        \
+         Solution::new::<grace_hopper::GraceHopper>(7, \"grace_hopper\"),\n    ]\n"
+    ));
+    let manifest = read("day-7/Cargo.toml");
+    assert!(manifest.contains(
+        "itertools = { version = \"^0.9\", optional = true }\n\n\
+         [features]\ngrace_hopper = [\"itertools\"]\n\n[[bin]]"
+    ));
+    assert!(manifest.ends_with(
+        "[[bin]]\nname = \"day-7-grace_hopper\"\npath = \"src/bin/grace_hopper.rs\"\n\
+         required-features = [\"grace_hopper\"]\n"
+    ));
+    let runner = read("aoc/Cargo.toml");
+    assert!(runner.contains("grace_hopper = [\"day-7/grace_hopper\"]"));
+    assert!(runner.contains("default = [\"vickz84259\", \"grace_hopper\"]"));
+    assert!(grace.apply(&root).unwrap().is_empty());
+
+    // vickz84259's feature is there already, so only needs the new day.
+    let vickz = Scaffold {
+        day: 9,
+        author: "vickz84259".to_string(),
+        gated: true,
+        dependencies: vec![],
+    };
+    vickz.apply(&root).unwrap();
+    assert!(
+        read("aoc/Cargo.toml").contains("    \"day-5/vickz84259\",\n    \"day-9/vickz84259\",\n]")
+    );
+    assert!(read("day-9/src/lib.rs").contains(
+        "    vec![\n        Solution::new::<ada::Ada>(9, \"ada\"),\n        \
+         #[cfg(feature = \"vickz84259\")]\n"
+    ));
+
+    assert!(Scaffold {
+        author: "Bad-Name".to_string(),
+        ..ada
+    }
+    .apply(&root)
+    .is_err());
+    fs::remove_dir_all(&root).unwrap();
+}
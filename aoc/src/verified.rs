@@ -0,0 +1,184 @@
+// Answers accepted on the puzzle site, kept so that every later run can be
+// checked against them without going online.
+//
+// Each answer is one tab-separated line: the day, the part, a hash of the
+// input it was accepted for, and the answer. Lines starting with `#` are
+// comments. Inputs are told apart by hash alone, so the inputs themselves
+// needn't be in the repository.
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use aoc_common::{parse, Answer, ParseError, Part, Result};
+
+use crate::workspace_dir;
+
+const HEADER: &str = "# day\tpart\tinput_hash\tanswer";
+
+/// Where the answers are kept unless told otherwise: next to the workspace's
+/// `Cargo.toml`, committed along with the solutions.
+pub fn default_path() -> PathBuf {
+    workspace_dir().join("verified-answers.tsv")
+}
+
+/// The 64-bit FNV-1a hash of `input`, in hex: short, stable across platforms
+/// and Rust versions, and plenty to tell a few dozen inputs apart.
+pub fn hash(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+/// How an answer compares with the one accepted for the same input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Verified,
+    /// No answer has been accepted for this input and part yet.
+    Unverified,
+    Mismatch {
+        verified: String,
+    },
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Verified => f.write_str("verified"),
+            Verdict::Unverified => f.write_str("unverified"),
+            Verdict::Mismatch { verified } => {
+                write!(f, "MISMATCH, verified answer is {}", verified)
+            }
+        }
+    }
+}
+
+/// Every accepted answer, by day, part and input hash.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Verified(BTreeMap<(u8, Part, String), String>);
+
+impl Verified {
+    /// The answers in the file at `path`. A missing file has none.
+    pub fn load(path: &Path) -> Result<Verified> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Verified::default()),
+            Err(e) => return Err(e.into()),
+        };
+        let entries: Vec<_> = parse::lines(&text, |line| {
+            if line.starts_with('#') || line.trim().is_empty() {
+                Ok(None)
+            } else {
+                parse_entry(line).map(Some)
+            }
+        })
+        .map_err(|e| e.in_file(path.display()))?;
+        Ok(Verified(entries.into_iter().flatten().collect()))
+    }
+
+    /// Writes every answer to `path`, ordered by day, part and hash.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut file = fs::File::create(path)?;
+        writeln!(file, "{}", HEADER)?;
+        for ((day, part, hash), answer) in &self.0 {
+            let part = part.to_string().to_lowercase();
+            writeln!(file, "{}\t{}\t{}\t{}", day, part, hash, answer)?;
+        }
+        Ok(())
+    }
+
+    /// Compares `answer` with the one accepted for `input`. Unsolved parts
+    /// are never verified.
+    pub fn check(&self, day: u8, part: Part, input: &str, answer: &Answer) -> Verdict {
+        match (answer, self.0.get(&(day, part, hash(input)))) {
+            (Answer::Unsolved, _) | (_, None) => Verdict::Unverified,
+            (answer, Some(verified)) if answer.to_string() == *verified => Verdict::Verified,
+            (_, Some(verified)) => Verdict::Mismatch {
+                verified: verified.clone(),
+            },
+        }
+    }
+
+    /// Accepts `answer` for `input`, returning the answer it replaces, if
+    /// any. Unsolved parts aren't recorded.
+    pub fn record(&mut self, day: u8, part: Part, input: &str, answer: &Answer) -> Option<String> {
+        if *answer == Answer::Unsolved {
+            return None;
+        }
+        self.0
+            .insert((day, part, hash(input)), answer.to_string())
+            .filter(|previous| *previous != answer.to_string())
+    }
+}
+
+fn parse_entry(line: &str) -> Result<((u8, Part, String), String), ParseError> {
+    let mut fields = line.split('\t');
+    let mut field = |expected| {
+        fields
+            .next()
+            .ok_or_else(|| ParseError::missing(line, expected))
+    };
+    let day = field("a day")?;
+    let part = field("a part")?;
+    let hash = field("an input hash")?.to_string();
+    let answer = field("an answer")?.to_string();
+    Ok((
+        (
+            parse::value(line, day, "a day")?,
+            parse::value(line, part, "`one` or `two`")?,
+            hash,
+        ),
+        answer,
+    ))
+}
+
+#[test]
+fn test_verified() {
+    assert_eq!("cbf29ce484222325", hash(""));
+    assert_eq!("af63dc4c8601ec8c", hash("a"));
+
+    let input = "1721\n979\n366\n299\n675\n1456\n";
+    let mut verified = Verified::default();
+    let answer = Answer::Int(514579);
+    assert_eq!(
+        Verdict::Unverified,
+        verified.check(1, Part::One, input, &answer)
+    );
+
+    assert_eq!(None, verified.record(1, Part::One, input, &answer));
+    assert_eq!(
+        None,
+        verified.record(1, Part::Two, input, &Answer::Unsolved)
+    );
+    assert_eq!(
+        Verdict::Verified,
+        verified.check(1, Part::One, input, &answer)
+    );
+    assert_eq!(
+        Verdict::Mismatch {
+            verified: "514579".to_string()
+        },
+        verified.check(1, Part::One, input, &Answer::Int(42))
+    );
+    assert_eq!(
+        Verdict::Unverified,
+        verified.check(1, Part::One, "979\n", &answer)
+    );
+    assert_eq!(
+        Verdict::Unverified,
+        verified.check(1, Part::Two, input, &answer)
+    );
+
+    let line = format!("1\tone\t{}\t514579", hash(input));
+    let parsed: Verified = Verified(vec![parse_entry(&line).unwrap()].into_iter().collect());
+    assert_eq!(verified, parsed);
+    assert!(parse_entry("1\tthree\tcbf29ce484222325\t1").is_err());
+    assert!(parse_entry("1\tone").is_err());
+
+    assert_eq!(
+        Some("514579".to_string()),
+        verified.record(1, Part::One, input, &Answer::Int(42))
+    );
+}
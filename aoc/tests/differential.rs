@@ -0,0 +1,46 @@
+// Random inputs fed to every solution of each day, looking for any the
+// solutions disagree on.
+//
+// Some disagreements are known bugs, waiting to be fixed. The harness must
+// keep finding those, shrunk to the input that shows the bug, and mustn't
+// find any others.
+use aoc::differential::{Counterexample, Property};
+
+const SEED: u64 = 1;
+const CASES: u64 = 50;
+
+fn check(day: u8) -> Option<Counterexample> {
+    let property = Property::for_day(day).unwrap();
+    property.check(&property.solutions(), SEED, CASES)
+}
+
+#[test]
+fn test_agreement() {
+    for day in [2, 3, 5, 6, 7, 8] {
+        if let Some(counterexample) = check(day) {
+            panic!("{}", counterexample);
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "vickz84259")]
+fn test_known_disagreements_vickz84259() {
+    use aoc_common::Part;
+
+    // vickz84259 keeps the entries in a `HashSet`, and so can pair an entry of
+    // 1010 with itself.
+    let day_1 = check(1).unwrap();
+    assert_eq!(Part::One, day_1.part);
+    assert!(day_1.input.lines().any(|entry| entry == "1010"));
+
+    // vickz84259 doesn't check the length of hair colours.
+    let day_4 = check(4).unwrap();
+    assert_eq!(Part::Two, day_4.part);
+    let hair = day_4
+        .input
+        .split_whitespace()
+        .find_map(|field| field.strip_prefix("hcl:#"))
+        .unwrap();
+    assert_ne!(6, hair.len());
+}
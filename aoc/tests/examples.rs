@@ -0,0 +1,36 @@
+// Every registered solution, and every variant, against every example of its
+// day.
+use aoc::examples::{self, Check};
+use aoc::registry;
+
+#[test]
+fn test_examples() {
+    let mut days: Vec<_> = registry::all().iter().map(|s| s.day).collect();
+    days.dedup();
+
+    let mut failures = vec![];
+    for day in days {
+        let examples = examples::load(day).unwrap();
+        assert!(!examples.is_empty(), "day {} has no examples", day);
+
+        let solutions = registry::find(day, None)
+            .into_iter()
+            .chain(registry::find_variants(day, None));
+        for solution in solutions {
+            for example in &examples {
+                for (part, check) in example.check(&solution) {
+                    if let Check::Failed(reason) = check {
+                        failures.push(format!(
+                            "{} on {}, part {}: {}",
+                            solution.name(),
+                            example,
+                            part,
+                            reason
+                        ));
+                    }
+                }
+            }
+        }
+    }
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
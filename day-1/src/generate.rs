@@ -0,0 +1,74 @@
+// Random expense reports with exactly one pair and one triple of entries that
+// sum to 2020.
+//
+// The pair and the triple are made of different entries of at least 600, and
+// every other entry is over 1420, so no other pair or triple can add up to
+// 2020.
+use aoc_common::generate::{Generated, Rng};
+
+/// An expense report of `size` entries, at least 5.
+pub fn input(rng: &mut Rng, size: usize) -> Generated {
+    let (pair, triple) = loop {
+        let a = rng.range(600..=1009u32);
+        let c = rng.range(600..=820u32);
+        let d = rng.range(600..=1420 - c);
+        let pair = [a, 2020 - a];
+        let triple = [c, d, 2020 - c - d];
+        if distinct(&pair, &triple) && sums(&pair, &triple) == (1, 1) {
+            break (pair, triple);
+        }
+    };
+
+    let mut filler: Vec<u32> = (1421..=1999).collect();
+    rng.shuffle(&mut filler);
+    let mut entries: Vec<u32> = pair
+        .iter()
+        .chain(&triple)
+        .copied()
+        .chain(filler.into_iter().cycle().take(size.saturating_sub(5)))
+        .collect();
+    rng.shuffle(&mut entries);
+
+    let input = entries.iter().map(|entry| format!("{}\n", entry)).collect();
+    Generated::new(input, pair[0] * pair[1], triple[0] * triple[1] * triple[2])
+}
+
+fn distinct(pair: &[u32], triple: &[u32]) -> bool {
+    let mut entries: Vec<_> = pair.iter().chain(triple).collect();
+    entries.sort();
+    entries.windows(2).all(|w| w[0] != w[1])
+}
+
+/// How many pairs and triples of different entries sum to 2020.
+fn sums(pair: &[u32], triple: &[u32]) -> (usize, usize) {
+    let entries: Vec<_> = pair.iter().chain(triple).collect();
+    let (mut pairs, mut triples) = (0, 0);
+    for i in 0..entries.len() {
+        for j in i + 1..entries.len() {
+            if entries[i] + entries[j] == 2020 {
+                pairs += 1;
+            }
+            for k in j + 1..entries.len() {
+                if entries[i] + entries[j] + entries[k] == 2020 {
+                    triples += 1;
+                }
+            }
+        }
+    }
+    (pairs, triples)
+}
+
+#[test]
+fn test_generate() {
+    use aoc_common::Solver;
+
+    use crate::matt::Matt;
+
+    for seed in 0..20 {
+        let generated = input(&mut Rng::new(seed), 200);
+        assert_eq!(200, generated.input.lines().count());
+        let report = Matt::parse(&generated.input).unwrap();
+        assert_eq!(generated.one, Matt::part_one(&report));
+    }
+    assert_eq!(5, input(&mut Rng::new(0), 0).input.lines().count());
+}
@@ -0,0 +1,26 @@
+// --- Day 1: Report Repair --
+//
+// https://adventofcode.com/2020/day/1
+use aoc_common::params::Parameter;
+use aoc_common::Solution;
+
+pub mod generate;
+pub mod matt;
+#[cfg(feature = "vickz84259")]
+pub mod vickz84259;
+
+/// The puzzle's constants, which every author's solution takes.
+pub const PARAMETERS: &[Parameter] = &[Parameter {
+    name: "target",
+    default: "2020",
+    about: "what the entries must sum to",
+}];
+
+/// Every author's solution for the day.
+pub fn solutions() -> Vec<Solution> {
+    vec![
+        Solution::new::<matt::Matt>(1, "matt"),
+        #[cfg(feature = "vickz84259")]
+        Solution::new::<vickz84259::Vickz84259>(1, "vickz84259"),
+    ]
+}
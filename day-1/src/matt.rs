@@ -1,42 +1,82 @@
 // --- Day 1: Report Repair --
 //
 // https://adventofcode.com/2020/day/1
+use aoc_common::json::Json;
+use aoc_common::params::{Parameter, Params};
+use aoc_common::{parse, Answer, Part, Result, Solver};
 
-fn fix_expense_report(report: &[i32]) -> i32 {
+/// Matt's solution: tries every pair of entries in turn.
+pub struct Matt;
+
+/// The expense report, with what its two entries must sum to.
+pub struct ExpenseReport {
+    pub entries: Vec<i32>,
+    pub target: i32,
+}
+
+impl Solver for Matt {
+    type Input = ExpenseReport;
+
+    const PARAMETERS: &'static [Parameter] = crate::PARAMETERS;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Self::parse_with(input, &Params::defaults(Self::PARAMETERS))
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Input> {
+        Ok(ExpenseReport {
+            entries: parse::lines(input, |line| parse::value(line, line, "an expense entry"))?,
+            target: params.get("target", "a number")?,
+        })
+    }
+
+    fn part_one(report: &Self::Input) -> Answer {
+        fix_expense_report(&report.entries, report.target).map_or(Answer::Unsolved, Answer::from)
+    }
+
+    fn auxiliary(report: &Self::Input, part: Part) -> Vec<(&'static str, Json)> {
+        match (part, find_entries(&report.entries, report.target)) {
+            (Part::One, Some((i, j))) => vec![("entries", vec![i, j].into())],
+            _ => vec![],
+        }
+    }
+}
+
+/// The product of the two entries that sum to `target`, if any do.
+pub fn fix_expense_report(report: &[i32], target: i32) -> Option<i32> {
+    find_entries(report, target).map(|(i, j)| i * j)
+}
+
+/// The first two entries that sum to `target`, if any do.
+pub fn find_entries(report: &[i32], target: i32) -> Option<(i32, i32)> {
     for (idx1, i) in report.iter().enumerate() {
         for (idx2, j) in report.iter().enumerate() {
-            if (idx1 != idx2) && (i + j == 2020) {
-                return i * j;
+            if (idx1 != idx2) && (i + j == target) {
+                return Some((*i, *j));
             }
         }
     }
-    0
+    None
 }
-fn main() {
-    let input = vec![
-        1864, 1192, 1802, 1850, 1986, 1514, 1620, 1910, 1557, 1529, 1081, 1227, 1869, 1545, 1064,
-        1509, 1060, 1590, 1146, 1855, 667, 1441, 1241, 1473, 1321, 1429, 1534, 1959, 1188, 1597,
-        1256, 1673, 1879, 1821, 1423, 1838, 1392, 1941, 1124, 1629, 1780, 1271, 1190, 1680, 1379,
-        1601, 1670, 1916, 1787, 1844, 2000, 1672, 1276, 1896, 1746, 1369, 1687, 1263, 1948, 1159,
-        1710, 1304, 1806, 1709, 1286, 1635, 1075, 1125, 1607, 1408, 1903, 1143, 1736, 1266, 1645,
-        1571, 1488, 1200, 211, 1148, 1585, 2005, 1724, 1071, 1690, 1189, 1101, 1315, 1452, 1622,
-        1074, 1486, 1209, 1253, 1422, 1235, 1354, 1399, 1675, 241, 1229, 1136, 1901, 1453, 1344,
-        1685, 1985, 1455, 1764, 1634, 1935, 1386, 1772, 1174, 1743, 1818, 1156, 1221, 167, 1398,
-        1552, 1816, 1197, 1829, 1930, 1812, 1983, 1185, 1579, 1928, 1892, 1978, 1720, 1584, 1506,
-        1245, 1539, 1653, 1876, 1883, 1982, 1114, 1406, 2002, 1765, 1175, 1947, 1519, 1943, 1566,
-        1361, 1830, 1679, 999, 1366, 1575, 1556, 1555, 1065, 1606, 1508, 1548, 1162, 1664, 1525,
-        1925, 1975, 1384, 1076, 1790, 1656, 1578, 1671, 1424, 757, 1485, 1677, 1583, 1395, 1793,
-        1111, 1522, 1195, 1128, 1123, 1151, 1568, 1559, 1331, 1191, 1753, 1630, 1979, 953, 1480,
-        1655, 1100, 1419, 1560, 1667,
-    ];
-
-    println!("Solution: {}", fix_expense_report(&input));
+
+/// Prints the answers for `input`.
+pub fn main(input: &str) -> Result<()> {
+    let report = Matt::parse(input)?;
+
+    match fix_expense_report(&report.entries, report.target) {
+        Some(solution) => println!("Solution: {}", solution),
+        None => println!("No two entries sum to {}", report.target),
+    }
+    Ok(())
 }
 
 #[test]
-fn test_solution() {
-    assert_eq!(
-        fix_expense_report(&vec![1721, 979, 366, 299, 675, 1456]),
-        514579
-    )
+fn test_target() {
+    let params = Params::from_args(&["target=10"]).unwrap();
+    let report = Matt::parse_with("1\n4\n6\n", &params.resolve(Matt::PARAMETERS).unwrap()).unwrap();
+    assert_eq!(Answer::Int(24), Matt::part_one(&report));
+    // Nothing sums to 2020.
+    let report = Matt::parse("1\n4\n6\n").unwrap();
+    assert_eq!(Answer::Unsolved, Matt::part_one(&report));
+    assert!(Matt::auxiliary(&report, Part::One).is_empty());
 }
@@ -1,63 +1,136 @@
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::result::Result;
-use std::time::Instant;
 
+use aoc_common::json::Json;
+use aoc_common::params::{Parameter, Params};
+use aoc_common::{parse, Answer, ParseError, Part, Result, Solver};
 use itertools::Itertools;
 
-fn get_entries() -> HashSet<u32> {
-    let file = File::open("input_1.txt").expect("Unable to read file");
-    let lines = io::BufReader::new(file).lines();
+/// vickz84259's solution: looks up what each entry is missing in a set of
+/// the entries.
+pub struct Vickz84259;
 
-    lines
-        .filter_map(Result::ok)
-        .map(|x| x.parse().unwrap())
-        .collect()
+/// The entries, with what they must sum to.
+pub struct Entries {
+    pub entries: HashSet<u32>,
+    pub target: u32,
 }
 
-fn part_1(entries: &HashSet<u32>) {
-    println!("Part 1:");
+impl Solver for Vickz84259 {
+    type Input = Entries;
 
-    let (entry_1, entry_2) = entries
-        .iter()
-        .map(|entry| (entry, 2020 - entry))
-        .filter(|x| entries.contains(&x.1))
-        .next()
-        .unwrap();
+    const PARAMETERS: &'static [Parameter] = crate::PARAMETERS;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Self::parse_with(input, &Params::defaults(Self::PARAMETERS))
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Input> {
+        Ok(Entries {
+            entries: get_entries(input)?,
+            target: params.get("target", "a number")?,
+        })
+    }
+
+    fn part_one(input: &Self::Input) -> Answer {
+        find_pair(&input.entries, input.target).map_or(Answer::Unsolved, |(entry_1, entry_2)| {
+            (entry_1 * entry_2).into()
+        })
+    }
+
+    fn part_two(input: &Self::Input) -> Answer {
+        find_triple(&input.entries, input.target)
+            .map_or(Answer::Unsolved, |(entry_1, entry_2, entry_3)| {
+                (entry_1 * entry_2 * entry_3).into()
+            })
+    }
 
-    println!("Values: {} and {}", entry_1, entry_2);
-    println!("Answer: {}", entry_1 * entry_2);
+    fn auxiliary(input: &Self::Input, part: Part) -> Vec<(&'static str, Json)> {
+        let entries = match part {
+            Part::One => find_pair(&input.entries, input.target)
+                .map(|(entry_1, entry_2)| vec![entry_1, entry_2]),
+            Part::Two => find_triple(&input.entries, input.target)
+                .map(|(entry_1, entry_2, entry_3)| vec![entry_1, entry_2, entry_3]),
+        };
+        entries.map_or(vec![], |entries| vec![("entries", entries.into())])
+    }
 }
 
-fn part_2(entries: &HashSet<u32>) {
-    println!("Part 2");
+/// Parses the expense report, one entry per line.
+pub fn get_entries(input: &str) -> Result<HashSet<u32>, ParseError> {
+    parse::lines(input, |x| parse::value(x, x, "an expense entry"))
+}
+
+/// Two entries that sum to `target`, if any do. An entry of half the target
+/// is paired with itself.
+pub fn find_pair(entries: &HashSet<u32>, target: u32) -> Option<(u32, u32)> {
+    entries
+        .iter()
+        .filter_map(|entry| Some((*entry, target.checked_sub(*entry)?)))
+        .find(|x| entries.contains(&x.1))
+}
 
+fn part_1(entries: &HashSet<u32>, target: u32) {
+    println!("Part 1:");
+
+    match find_pair(entries, target) {
+        Some((entry_1, entry_2)) => {
+            println!("Values: {} and {}", entry_1, entry_2);
+            println!("Answer: {}", entry_1 * entry_2);
+        }
+        None => println!("No two entries sum to {}", target),
+    }
+}
+
+/// Three entries that sum to `target`, if any do.
+pub fn find_triple(entries: &HashSet<u32>, target: u32) -> Option<(u32, u32, u32)> {
     let combinations = entries.iter().tuple_combinations::<(&u32, &u32)>();
     let addition = combinations.map(|x| (x.0, x.1, x.0 + x.1));
-    let subtraction = addition
-        .filter(|x| x.2 < 2020)
-        .map(|x| (x.0, x.1, 2020 - x.2));
+    let mut subtraction = addition
+        .filter(|x| x.2 < target)
+        .map(|x| (x.0, x.1, target - x.2));
+
+    let (entry_1, entry_2, entry_3) = subtraction.find(|x| entries.contains(&x.2))?;
+    Some((*entry_1, *entry_2, entry_3))
+}
 
-    let (entry_1, entry_2, entry_3) = subtraction
-        .filter(|x| entries.contains(&x.2))
-        .next()
-        .unwrap();
+fn part_2(entries: &HashSet<u32>, target: u32) {
+    println!("Part 2");
 
-    println!("Values: {}, {} and {}", entry_1, entry_2, entry_3);
-    println!("Answer: {}", entry_1 * entry_2 * entry_3);
+    match find_triple(entries, target) {
+        Some((entry_1, entry_2, entry_3)) => {
+            println!("Values: {}, {} and {}", entry_1, entry_2, entry_3);
+            println!("Answer: {}", entry_1 * entry_2 * entry_3);
+        }
+        None => println!("No three entries sum to {}", target),
+    }
 }
 
-fn main() {
-    let entries = get_entries();
+/// Prints the answers for `input`, along with the entries that make them up.
+pub fn main(input: &str) -> Result<()> {
+    let Entries { entries, target } = Vickz84259::parse(input)?;
 
-    let mut start = Instant::now();
-    part_1(&entries);
-    println!("Time Taken: {:?}", start.elapsed());
+    part_1(&entries, target);
 
     println!("---------------");
 
-    start = Instant::now();
-    part_2(&entries);
-    println!("Time Taken: {:?}", start.elapsed());
+    part_2(&entries, target);
+    Ok(())
+}
+
+#[test]
+fn test_target() {
+    let params = Params::from_args(&["target=10"]).unwrap();
+    let entries = Vickz84259::parse_with(
+        "1\n2\n7\n9\n",
+        &params.resolve(Vickz84259::PARAMETERS).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(Answer::Int(9), Vickz84259::part_one(&entries));
+    assert_eq!(Answer::Int(14), Vickz84259::part_two(&entries));
+
+    // Nothing sums to 2020.
+    let entries = Vickz84259::parse("1\n2\n").unwrap();
+    assert_eq!(Answer::Unsolved, Vickz84259::part_one(&entries));
+    assert_eq!(Answer::Unsolved, Vickz84259::part_two(&entries));
+    assert!(Vickz84259::auxiliary(&entries, Part::One).is_empty());
 }
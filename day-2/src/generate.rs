@@ -0,0 +1,53 @@
+// Random password databases.
+//
+// Passwords are drawn mostly from the policy's letter so that a good share of
+// them pass each policy; which ones do is worked out as they're made.
+use aoc_common::generate::{Generated, Rng};
+
+const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+/// A database of `size` passwords, each with its policy.
+pub fn input(rng: &mut Rng, size: usize) -> Generated {
+    let mut input = String::new();
+    let (mut by_count, mut by_position) = (0, 0);
+    for _ in 0..size {
+        let length = rng.range(3..=20);
+        let low = rng.range(1..=length - 1);
+        let high = rng.range(low + 1..=length);
+        let letter = *rng.choose(LETTERS) as char;
+        let password: String = (0..length)
+            .map(|_| {
+                if rng.chance(0.3) {
+                    letter
+                } else {
+                    *rng.choose(LETTERS) as char
+                }
+            })
+            .collect();
+
+        let count = password.matches(letter).count();
+        if (low..=high).contains(&count) {
+            by_count += 1;
+        }
+        let at = |position: usize| password.as_bytes()[position - 1] == letter as u8;
+        if at(low) != at(high) {
+            by_position += 1;
+        }
+        input.push_str(&format!("{}-{} {}: {}\n", low, high, letter, password));
+    }
+    Generated::new(input, by_count, by_position)
+}
+
+#[test]
+fn test_generate() {
+    use aoc_common::Solver;
+
+    use crate::matt::Matt;
+
+    for seed in 0..20 {
+        let generated = input(&mut Rng::new(seed), 100);
+        let passwords = Matt::parse(&generated.input).unwrap();
+        assert_eq!(generated.one, Matt::part_one(&passwords));
+        assert_eq!(generated.two, Matt::part_two(&passwords));
+    }
+}
@@ -0,0 +1,18 @@
+// --- Day 2: Password Philosophy--
+//
+// https://adventofcode.com/2020/day/2
+use aoc_common::Solution;
+
+pub mod generate;
+pub mod matt;
+#[cfg(feature = "vickz84259")]
+pub mod vickz84259;
+
+/// Every author's solution for the day.
+pub fn solutions() -> Vec<Solution> {
+    vec![
+        Solution::new::<matt::Matt>(2, "matt"),
+        #[cfg(feature = "vickz84259")]
+        Solution::new::<vickz84259::Vickz84259>(2, "vickz84259"),
+    ]
+}
@@ -1,18 +1,42 @@
 // --- Day 2: Password Philosophy--
 //
 // https://adventofcode.com/2020/day/2
-use std::fs::File;
-use std::io::{self, BufRead};
-
-struct PwdEntry {
-    low: i32,
-    high: i32,
-    pat: char,
-    pwd: String,
+use aoc_common::{parse, Answer, ParseError, Result, Solver};
+
+/// Matt's solution.
+pub struct Matt;
+
+impl Solver for Matt {
+    type Input = Vec<PwdEntry>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::lines(input, parse_input_line)?)
+    }
+
+    fn part_one(passwords: &Self::Input) -> Answer {
+        valid_passwords(passwords).into()
+    }
+
+    fn part_two(passwords: &Self::Input) -> Answer {
+        valid_passwords2(passwords).into()
+    }
+}
+
+/// A password, and the policy it was set under.
+pub struct PwdEntry {
+    pub low: i32,
+    pub high: i32,
+    /// The letter the policy is about.
+    pub pat: char,
+    pub pwd: String,
 }
 
-enum ValidationAlgo {
+/// Which of the policies' meanings to check passwords against.
+pub enum ValidationAlgo {
+    /// The letter must appear from `low` to `high` times.
     One,
+    /// The letter must be at exactly one of positions `low` and `high`,
+    /// counting from 1.
     Two,
 }
 
@@ -27,7 +51,8 @@ impl PwdEntry {
     }
 }
 
-fn valid_passwords(passwords: &[PwdEntry]) -> i32 {
+/// How many passwords are valid under the policy's first meaning.
+pub fn valid_passwords(passwords: &[PwdEntry]) -> i32 {
     let mut valid = 0;
     for entry in passwords {
         if is_valid(entry, ValidationAlgo::One) {
@@ -37,7 +62,8 @@ fn valid_passwords(passwords: &[PwdEntry]) -> i32 {
     valid
 }
 
-fn valid_passwords2(passwords: &[PwdEntry]) -> i32 {
+/// How many passwords are valid under the policy's second meaning.
+pub fn valid_passwords2(passwords: &[PwdEntry]) -> i32 {
     let mut valid = 0;
     for entry in passwords {
         if is_valid(entry, ValidationAlgo::Two) {
@@ -46,78 +72,68 @@ fn valid_passwords2(passwords: &[PwdEntry]) -> i32 {
     }
     valid
 }
-fn is_valid(entry: &PwdEntry, algo: ValidationAlgo) -> bool {
+
+/// Whether `entry` is valid under `algo`.
+pub fn is_valid(entry: &PwdEntry, algo: ValidationAlgo) -> bool {
     match algo {
         ValidationAlgo::One => {
             let m = entry.pwd.matches(entry.pat).count() as i32;
-            if m >= entry.low && m <= entry.high {
-                true
-            } else {
-                false
-            }
+            m >= entry.low && m <= entry.high
         }
         ValidationAlgo::Two => {
             let m: Vec<_> = entry.pwd.match_indices(entry.pat).map(|x| x.0).collect();
             let low: usize = entry.low as usize - 1;
             let high: usize = entry.high as usize - 1;
-            if m.contains(&low) && m.contains(&high) {
-                false
-            } else if m.contains(&low) || m.contains(&high) {
-                true
-            } else {
-                false
-            }
+            m.contains(&low) != m.contains(&high)
         }
     }
 }
-fn main() {
-    let input = input().unwrap();
+
+/// Prints the answers for `input`.
+pub fn main(input: &str) -> Result<()> {
+    let input = Matt::parse(input)?;
 
     println!("Part One: {}", valid_passwords(&input));
     println!("Part Two: {}", valid_passwords2(&input));
+    Ok(())
 }
 
-fn input() -> io::Result<Vec<PwdEntry>> {
-    let mut input = vec![];
-    let file = File::open("src/matt.txt")?;
-    let buf = io::BufReader::new(file);
-    for item in buf.lines() {
-        let line = item?;
-        input.push(parse_input_line(&line).unwrap())
+/// Parses a line like `1-3 a: abcde`. Both numbers count from 1, and the
+/// second mustn't be below the first.
+pub fn parse_input_line(line: &str) -> Result<PwdEntry, ParseError> {
+    let (policy, pwd) = parse::split_once(line, line, ": ", "`<policy>: <password>`")?;
+    let (low_high, chr) = parse::split_once(line, policy, " ", "`<low>-<high> <letter>`")?;
+    let (low_span, high_span) = parse::split_once(line, low_high, "-", "`<low>-<high>`")?;
+    let low: i32 = parse::value(line, low_span, "a number")?;
+    if low < 1 {
+        return Err(ParseError::at(line, low_span, "a number from 1"));
     }
-    Ok(input)
-}
-
-fn parse_input_line(line: &str) -> Result<PwdEntry, std::num::ParseIntError> {
-    let parts: Vec<_> = line.split(' ').collect();
-    let low_high: Vec<_> = parts[0].split('-').collect();
-    let low: i32 = low_high[0].parse()?;
-    let high: i32 = low_high[1].parse()?;
-    let chr: char = parts[1].split(':').collect::<Vec<_>>()[0]
-        .chars()
-        .next()
-        .unwrap();
-    let pwd = parts[2].to_string();
+    let high: i32 = parse::value(line, high_span, "a number")?;
+    if high < low {
+        let expected = format!("a number from {}", low);
+        return Err(ParseError::at(line, high_span, &expected));
+    }
+    let chr: char = parse::value(line, chr, "a single letter")?;
 
-    Ok(PwdEntry::new((low, high, chr, pwd)))
+    Ok(PwdEntry::new((low, high, chr, pwd.to_string())))
 }
 
 #[test]
-fn test_matt() {
+fn test_parse_error() {
+    let e = parse::lines::<_, Vec<_>, _>("1-3 a: abcde\n1-x b: cdefg", parse_input_line)
+        .err()
+        .unwrap();
+    assert_eq!((2, 3, "x"), (e.line, e.column, e.found.as_str()));
+
+    let e = parse_input_line("0-3 a: abc").err().unwrap();
     assert_eq!(
-        valid_passwords(&vec![
-            PwdEntry::new((1, 3, 'a', "abcde".to_string())),
-            PwdEntry::new((1, 3, 'b', "cdefg".to_string())),
-            PwdEntry::new((2, 9, 'c', "ccccccccc".to_string()))
-        ]),
-        2
+        (1, "0", "a number from 1"),
+        (e.column, e.found.as_str(), e.expected.as_str())
     );
+    let e = parse_input_line("3-2 a: abc").err().unwrap();
     assert_eq!(
-        valid_passwords2(&vec![
-            PwdEntry::new((1, 3, 'a', "abcde".to_string())),
-            PwdEntry::new((1, 3, 'b', "cdefg".to_string())),
-            PwdEntry::new((2, 9, 'c', "ccccccccc".to_string()))
-        ]),
-        1
+        (3, "2", "a number from 3"),
+        (e.column, e.found.as_str(), e.expected.as_str())
     );
+    assert!(parse_input_line("3-3 a: abc").is_ok());
 }
@@ -1,52 +1,86 @@
-use std::fs::File;
-use std::io::{self, BufRead};
-
+use aoc_common::{parse, Answer, ParseError, Result, Solver};
 use itertools::Itertools;
 
-fn read_lines() -> Vec<String> {
-    let file = File::open("input_2.txt").expect("Unable to read file");
-    io::BufReader::new(file)
-        .lines()
-        .filter_map(Result::ok)
-        .collect()
-}
+/// vickz84259's solution.
+pub struct Vickz84259;
 
-fn is_valid_password(input: &&String) -> bool {
-    let (policy, mut char_str, password) = input.split(" ").collect_tuple().unwrap();
+impl Solver for Vickz84259 {
+    type Input = Vec<Password>;
 
-    char_str = char_str.strip_suffix(':').unwrap();
-    let character: char = char_str.parse().unwrap();
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::lines(input, Password::parse)?)
+    }
 
-    let char_count = password.chars().filter(|x| x == &character).count();
+    fn part_one(passwords: &Self::Input) -> Answer {
+        part_1(passwords).into()
+    }
 
-    let (min, max) = policy
-        .split("-")
-        .map(|x| x.parse::<usize>().unwrap())
-        .collect_tuple()
-        .unwrap();
+    fn part_two(passwords: &Self::Input) -> Answer {
+        part_2(passwords).into()
+    }
+}
 
-    min <= char_count && char_count <= max
+/// A password, and the policy it was set under.
+pub struct Password {
+    /// The two numbers of the policy.
+    pub policy: (usize, usize),
+    pub character: char,
+    pub password: String,
 }
 
-fn part_1(lines: &Vec<String>) {
-    println!("Part 1:");
+impl Password {
+    /// Parses a line like `1-3 a: abcde`.
+    pub fn parse(line: &str) -> Result<Password, ParseError> {
+        let (policy, char_str, password) = line
+            .split(' ')
+            .collect_tuple()
+            .ok_or_else(|| ParseError::at(line, line, "`<min>-<max> <char>: <password>`"))?;
+
+        let char_str = char_str
+            .strip_suffix(':')
+            .ok_or_else(|| ParseError::at(line, char_str, "`<char>:`"))?;
+        let character: char = parse::value(line, char_str, "a single character")?;
+
+        let (min, max) = parse::split_once(line, policy, "-", "`<min>-<max>`")?;
+        let policy = (
+            parse::value(line, min, "a number")?,
+            parse::value(line, max, "a number")?,
+        );
+
+        Ok(Password {
+            policy,
+            character,
+            password: password.to_string(),
+        })
+    }
+}
+
+/// Whether the password has from `min` to `max` of the policy's character.
+pub fn is_valid_password(input: &&Password) -> bool {
+    let char_count = input
+        .password
+        .chars()
+        .filter(|x| x == &input.character)
+        .count();
 
-    let valid_count = lines.iter().filter(is_valid_password).count();
-    println!("Answer: {} passwords", valid_count);
+    let (min, max) = input.policy;
+
+    min <= char_count && char_count <= max
 }
 
-fn is_valid_password_2(input: &&String) -> bool {
-    let (policy, mut char_str, password) = input.split(" ").collect_tuple().unwrap();
-    char_str = char_str.strip_suffix(':').unwrap();
+/// How many passwords are valid under the policy's first meaning.
+pub fn part_1(lines: &[Password]) -> usize {
+    lines.iter().filter(is_valid_password).count()
+}
 
-    let (first, second) = policy
-        .split("-")
-        .map(|x| x.parse::<usize>().unwrap())
-        .collect_tuple()
-        .unwrap();
+/// Whether the policy's character is at exactly one of its two positions,
+/// counting from 1.
+pub fn is_valid_password_2(input: &&Password) -> bool {
+    let (first, second) = input.policy;
 
-    let no_of_matches = password
-        .match_indices(char_str)
+    let no_of_matches = input
+        .password
+        .match_indices(input.character)
         .map(|result| result.0 + 1)
         .filter(|index| index == &first || index == &second)
         .count();
@@ -54,18 +88,21 @@ fn is_valid_password_2(input: &&String) -> bool {
     no_of_matches == 1
 }
 
-fn part_2(lines: &Vec<String>) {
-    println!("Part 2:");
-
-    let valid_count = lines.iter().filter(is_valid_password_2).count();
-    println!("Answer: {} passwords", valid_count)
+/// How many passwords are valid under the policy's second meaning.
+pub fn part_2(lines: &[Password]) -> usize {
+    lines.iter().filter(is_valid_password_2).count()
 }
 
-fn main() {
-    let lines = read_lines();
-    part_1(&lines);
+/// Prints the answers for `input`.
+pub fn main(input: &str) -> Result<()> {
+    let lines = Vickz84259::parse(input)?;
+
+    println!("Part 1:");
+    println!("Answer: {} passwords", part_1(&lines));
 
     println!("----------");
 
-    part_2(&lines);
+    println!("Part 2:");
+    println!("Answer: {} passwords", part_2(&lines));
+    Ok(())
 }
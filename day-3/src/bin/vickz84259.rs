@@ -0,0 +1,9 @@
+use aoc_common::Solution;
+use day_3::vickz84259::{self, Vickz84259};
+
+fn main() {
+    aoc_common::input::run_with_input(
+        Solution::new::<Vickz84259>(3, "vickz84259"),
+        vickz84259::main,
+    )
+}
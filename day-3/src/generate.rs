@@ -0,0 +1,94 @@
+// Random toboggan maps of any size.
+use aoc_common::generate::{Generated, Rng};
+
+/// The slope part one asks about, and every slope part two does.
+const SLOPES: [(usize, usize); 5] = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+
+/// The shape of a map to generate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Map {
+    pub width: usize,
+    pub height: usize,
+    /// The chance of any square but the starting one having a tree.
+    pub trees: f64,
+}
+
+impl Default for Map {
+    /// As big and as wooded as the puzzle's maps.
+    fn default() -> Map {
+        Map {
+            width: 31,
+            height: 323,
+            trees: 0.2,
+        }
+    }
+}
+
+/// A map `size` squares high and as wide as the puzzle's.
+pub fn input(rng: &mut Rng, size: usize) -> Generated {
+    map(
+        rng,
+        &Map {
+            height: size,
+            ..Map::default()
+        },
+    )
+}
+
+/// A map shaped like `map`.
+pub fn map(rng: &mut Rng, map: &Map) -> Generated {
+    let (width, height) = (map.width.max(1), map.height.max(1));
+    let squares: Vec<Vec<bool>> = (0..height)
+        .map(|y| {
+            (0..width)
+                .map(|x| (x, y) != (0, 0) && rng.chance(map.trees))
+                .collect()
+        })
+        .collect();
+
+    let trees = |(right, down): (usize, usize)| {
+        (0..height)
+            .step_by(down)
+            .enumerate()
+            .filter(|&(step, y)| squares[y][step * right % width])
+            .count()
+    };
+    let input = squares
+        .iter()
+        .map(|row| {
+            let mut line: String = row
+                .iter()
+                .map(|&tree| if tree { '#' } else { '.' })
+                .collect();
+            line.push('\n');
+            line
+        })
+        .collect();
+    Generated::new(
+        input,
+        trees(SLOPES[1]),
+        SLOPES.iter().map(|&slope| trees(slope)).product::<usize>(),
+    )
+}
+
+#[test]
+fn test_generate() {
+    use aoc_common::Solver;
+
+    use crate::matt::Matt;
+
+    for seed in 0..20 {
+        let generated = input(&mut Rng::new(seed), 50);
+        let hill = Matt::parse(&generated.input).unwrap();
+        assert_eq!((31, 50), (hill.map.width(), hill.map.height()));
+        assert_eq!(generated.one, Matt::part_one(&hill));
+        assert_eq!(generated.two, Matt::part_two(&hill));
+    }
+
+    let tiny = Map {
+        width: 3,
+        height: 2,
+        trees: 1.0,
+    };
+    assert_eq!(".##\n###\n", self::map(&mut Rng::new(0), &tiny).input);
+}
@@ -0,0 +1,77 @@
+// --- Day 3: Toboggan Trajectory--
+//
+// https://adventofcode.com/2020/day/3
+use aoc_common::params::{self, Parameter, Params};
+use aoc_common::{Result, Solution};
+
+pub mod generate;
+pub mod matt;
+pub mod vickz84259;
+
+/// The puzzle's constants, which every author's solution takes.
+pub const PARAMETERS: &[Parameter] = &[
+    Parameter {
+        name: "slope",
+        default: "3:1",
+        about: "part one's slope, as right:down",
+    },
+    Parameter {
+        name: "slopes",
+        default: "1:1,3:1,5:1,7:1,1:2",
+        about: "part two's slopes, as right:down separated by commas",
+    },
+];
+
+/// The slopes each part goes down, as how far right and down the toboggan
+/// goes each step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Slopes {
+    pub part_one: (usize, usize),
+    pub part_two: Vec<(usize, usize)>,
+}
+
+impl Slopes {
+    /// The slopes in `params`, which has a value for each of [`PARAMETERS`].
+    pub fn from_params(params: &Params) -> Result<Slopes> {
+        let expected = "right:down, going down at least 1";
+        Ok(Slopes {
+            part_one: params.get_with("slope", expected, slope)?,
+            part_two: params.get_with("slopes", expected, |value| params::list(value, slope))?,
+        })
+    }
+}
+
+impl Default for Slopes {
+    /// The puzzle's slopes.
+    fn default() -> Slopes {
+        Slopes::from_params(&Params::defaults(PARAMETERS)).unwrap()
+    }
+}
+
+fn slope(value: &str) -> Option<(usize, usize)> {
+    let (right, down) = value.split_once(':')?;
+    match (right.parse().ok()?, down.parse().ok()?) {
+        (_, 0) => None,
+        slope => Some(slope),
+    }
+}
+
+/// Every author's solution for the day.
+pub fn solutions() -> Vec<Solution> {
+    vec![
+        Solution::new::<matt::Matt>(3, "matt"),
+        Solution::new::<vickz84259::Vickz84259>(3, "vickz84259"),
+    ]
+}
+
+/// Alternative implementations, which are only run to benchmark them against
+/// each other.
+pub fn variants() -> Vec<Solution> {
+    use vickz84259::{BitMap, BoolMap, DefaultMap, OnMap};
+
+    vec![
+        Solution::new::<OnMap<DefaultMap>>(3, "vickz84259").with_variant("default-map"),
+        Solution::new::<OnMap<BoolMap>>(3, "vickz84259").with_variant("bool-map"),
+        Solution::new::<OnMap<BitMap>>(3, "vickz84259").with_variant("bit-map"),
+    ]
+}
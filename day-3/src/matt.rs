@@ -2,126 +2,158 @@
 //
 // https://adventofcode.com/2020/day/3
 //use std::collections::VecDeque;
-use std::fs::File;
-use std::io::{self, BufRead};
+use aoc_common::grid::Grid;
+use aoc_common::params::{Parameter, Params};
+use aoc_common::{Answer, ParseError, Result, Solver};
 
+use crate::Slopes;
+
+/// Matt's solution.
+pub struct Matt;
+
+/// The map, with the slopes to go down it.
+pub struct Hill {
+    pub map: GridMap,
+    pub slopes: Slopes,
+}
+
+impl Solver for Matt {
+    type Input = Hill;
+
+    const PARAMETERS: &'static [Parameter] = crate::PARAMETERS;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Self::parse_with(input, &Params::defaults(Self::PARAMETERS))
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Input> {
+        Ok(Hill {
+            map: parse_map(input)?,
+            slopes: Slopes::from_params(params)?,
+        })
+    }
+
+    fn part_one(hill: &Self::Input) -> Answer {
+        let (right, down) = hill.slopes.part_one;
+        trees_encountered(&hill.map, &Slope::new(right, down)).into()
+    }
+
+    fn part_two(hill: &Self::Input) -> Answer {
+        trees_encountered_multiplied(
+            &hill.map,
+            hill.slopes
+                .part_two
+                .iter()
+                .map(|&(right, down)| Slope::new(right, down))
+                .collect(),
+        )
+        .into()
+    }
+}
+
+/// One square of the map.
 #[derive(Debug, Clone)]
-enum GridPoint {
+pub enum GridPoint {
     OpenSquare,
     Tree,
 }
-type GridLine = Vec<GridPoint>;
-type GridMap = Vec<GridLine>;
-struct Slope {
-    right: usize,
-    down: usize,
+/// The map. It repeats to the right forever.
+pub type GridMap = Grid<GridPoint>;
+
+/// How far right and down the toboggan goes each step.
+pub struct Slope {
+    pub right: usize,
+    pub down: usize,
 }
+
 impl Slope {
-    fn new(right: usize, down: usize) -> Slope {
+    pub fn new(right: usize, down: usize) -> Slope {
         Slope { right, down }
     }
 }
 
-fn trees_encountered(map: &GridMap, slope: &Slope) -> usize {
+/// How many trees the toboggan hits going down the map from the top left.
+pub fn trees_encountered(map: &GridMap, slope: &Slope) -> usize {
     let mut tree_count = 0;
     let mut right_offset = 0;
     let mut down_offset = 0;
-    for _ in 0..(map.len() - 1) {
+    for _ in 0..(map.height() - 1) {
         right_offset += slope.right;
         down_offset += slope.down;
-        if down_offset > map.len() - 1 {
+        if down_offset > map.height() - 1 {
             break;
         }
-        match map[down_offset][right_offset % map[0].len()] {
-            GridPoint::Tree => tree_count += 1,
-            _ => {}
+        if let GridPoint::Tree = map.get_wrapping(right_offset as isize, down_offset as isize) {
+            tree_count += 1;
         }
     }
     tree_count
 }
 
-fn trees_encountered_multiplied(map: &GridMap, slopes: Vec<Slope>) -> usize {
-    slopes
-        .iter()
-        .map(|s| trees_encountered(map, s))
-        .fold(1, |acc, x| acc * x)
+/// The product of the trees hit on each of the slopes.
+pub fn trees_encountered_multiplied(map: &GridMap, slopes: Vec<Slope>) -> usize {
+    slopes.iter().map(|s| trees_encountered(map, s)).product()
 }
 
-fn main() {
-    let map = input().unwrap();
-    let slope = Slope::new(3, 1);
+/// Prints the answers for `input`.
+pub fn main(input: &str) -> Result<()> {
+    let hill = Matt::parse(input)?;
 
-    println!("Part One: {}", trees_encountered(&map, &slope));
-    println!(
-        "Part Two: {}",
-        trees_encountered_multiplied(
-            &map,
-            vec!(
-                Slope::new(1, 1),
-                Slope::new(3, 1),
-                Slope::new(5, 1),
-                Slope::new(7, 1),
-                Slope::new(1, 2),
-            )
-        )
-    );
+    println!("Part One: {}", Matt::part_one(&hill));
+    println!("Part Two: {}", Matt::part_two(&hill));
+    Ok(())
 }
 
-fn input() -> io::Result<GridMap> {
-    let mut input = vec![];
-    let file = File::open("src/matt.txt")?;
-    let buf = io::BufReader::new(file);
-    for item in buf.lines() {
-        let line = item?;
-        input.push(parse_input_line(&line))
+/// Parses the map, one row per line, e.g. `..##.......`. It must have at
+/// least one square.
+pub fn parse_map(input: &str) -> Result<GridMap, ParseError> {
+    let map = Grid::parse(input, "`#` or `.`", |chr| match chr {
+        '#' => Some(GridPoint::Tree),
+        '.' => Some(GridPoint::OpenSquare),
+        _ => None,
+    })?;
+    if map.width() == 0 {
+        return Err(ParseError::at(input, &input[..0], "a row of `#` or `.`"));
     }
-    Ok(input)
+    Ok(map)
 }
 
-fn parse_input_line(line: &str) -> GridLine {
-    let mut grid_line = vec![];
-    for chr in line.chars() {
-        match chr {
-            '#' => grid_line.push(GridPoint::Tree),
-            '.' => grid_line.push(GridPoint::OpenSquare),
-            _ => unreachable!(),
-        }
+#[test]
+fn test_parse_error() {
+    let e = Matt::parse("..#\n.o.\n").err().unwrap().to_string();
+    assert_eq!(
+        "invalid input: line 2, column 2: expected `#` or `.`, found `o`\n  |\n2 | .o.\n  |  ^",
+        e
+    );
+    for input in ["", "\n"] {
+        let e = parse_map(input).err().unwrap();
+        assert_eq!(
+            (1, 1, "a row of `#` or `.`"),
+            (e.line, e.column, e.expected.as_str())
+        );
     }
-    grid_line
 }
 
 #[test]
-fn test_matt() {
-    let map_lines_str = vec![
-        "..##.......",
-        "#...#...#..",
-        ".#....#..#.",
-        "..#.#...#.#",
-        ".#...##..#.",
-        "..#.##.....",
-        ".#.#.#....#",
-        ".#........#",
-        "#.##...#...",
-        "#...##....#",
-        ".#..#...#.#",
-    ];
-    let map: Vec<_> = map_lines_str
-        .into_iter()
-        .map(|l| parse_input_line(l))
-        .collect();
-    let slope = Slope::new(3, 1);
-    assert_eq!(trees_encountered(&map, &slope), 7);
+fn test_slopes() {
+    let params = |args: &[&str]| {
+        Params::from_args(args)
+            .unwrap()
+            .resolve(Matt::PARAMETERS)
+            .unwrap()
+    };
+    let map = "..#\n#..\n.#.\n";
+    let hill = Matt::parse_with(map, &params(&["slope=2:1", "slopes=1:1"])).unwrap();
+    assert_eq!(Answer::Int(1), Matt::part_one(&hill));
+    assert_eq!(Answer::Int(0), Matt::part_two(&hill));
+    assert_eq!(Slopes::default(), Matt::parse(map).unwrap().slopes);
+
+    let e = Matt::parse_with(map, &params(&["slope=1:0"]))
+        .err()
+        .unwrap();
     assert_eq!(
-        trees_encountered_multiplied(
-            &map,
-            vec!(
-                Slope::new(1, 1),
-                Slope::new(3, 1),
-                Slope::new(5, 1),
-                Slope::new(7, 1),
-                Slope::new(1, 2),
-            )
-        ),
-        336
+        "invalid parameter: `slope=1:0`, expected right:down, going down at least 1",
+        e.to_string()
     );
+    assert!(Matt::parse_with(map, &params(&["slopes=1:1,3"])).is_err());
 }
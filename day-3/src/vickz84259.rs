@@ -1,102 +1,135 @@
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::time::Instant;
+use std::marker::PhantomData;
 
-type MapLines = Vec<String>;
+use aoc_common::grid::Grid;
+use aoc_common::params::{Parameter, Params};
+use aoc_common::{Answer, ParseError, Result, Solver};
 
-fn get_lines() -> MapLines {
-    let file = File::open("input_3.txt").expect("Unable to read file");
-    let lines = io::BufReader::new(file).lines();
+use crate::Slopes;
 
-    lines.filter_map(Result::ok).collect()
+/// Solves the puzzle on a `DefaultMap`.
+pub type Vickz84259 = OnMap<DefaultMap>;
+
+/// Solves the puzzle on any of the maps, so that they can be benchmarked
+/// against each other.
+pub struct OnMap<M>(PhantomData<M>);
+
+/// A map, with the slopes to go down it.
+pub struct Course<M> {
+    pub map: M,
+    pub slopes: Slopes,
 }
 
-trait Map {
-    fn new(lines: &MapLines) -> Self;
+impl<M: Map> Solver for OnMap<M> {
+    type Input = Course<M>;
+
+    const PARAMETERS: &'static [Parameter] = crate::PARAMETERS;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Self::parse_with(input, &Params::defaults(Self::PARAMETERS))
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Input> {
+        Ok(Course {
+            map: M::new(&get_map(input)?),
+            slopes: Slopes::from_params(params)?,
+        })
+    }
+
+    fn part_one(course: &Self::Input) -> Answer {
+        part_1(&course.map, course.slopes.part_one).into()
+    }
+
+    fn part_two(course: &Self::Input) -> Answer {
+        part_2(&course.map, &course.slopes.part_two).into()
+    }
+}
+
+/// Checks the map is only made of `.` and `#`, with at least one square, and
+/// reads it into a grid.
+pub fn get_map(input: &str) -> Result<Grid<char>, ParseError> {
+    let map = Grid::parse(input, "`.` or `#`", |c| match c {
+        '.' | '#' => Some(c),
+        _ => None,
+    })?;
+    if map.width() == 0 {
+        return Err(ParseError::at(input, &input[..0], "a row of `.` or `#`"));
+    }
+    Ok(map)
+}
+
+/// A way of storing the map, all of which find the same trees.
+pub trait Map {
+    fn new(map: &Grid<char>) -> Self;
+    /// How many trees there are going down the map from the top left,
+    /// `forward` squares right for every `down` squares down.
     fn traverse(&self, forward: usize, down: usize) -> usize;
 }
 
-struct DefaultMap {
-    _map: Vec<Vec<char>>,
+/// The map as a grid of characters.
+pub struct DefaultMap {
+    _map: Grid<char>,
 }
 
 impl Map for DefaultMap {
-    fn new(lines: &MapLines) -> Self {
-        let vecs = lines.iter().map(|line| line.chars().collect::<Vec<char>>());
-
-        DefaultMap {
-            _map: vecs.collect(),
-        }
+    fn new(map: &Grid<char>) -> Self {
+        DefaultMap { _map: map.clone() }
     }
 
     fn traverse(&self, forward: usize, down: usize) -> usize {
-        let vec_tuple = self._map.iter().step_by(down).enumerate();
+        let vec_tuple = (0..self._map.height()).step_by(down).enumerate();
 
         vec_tuple
             .filter(|x| {
-                let index = (forward * x.0) % x.1.len();
-                match x.1[index] {
-                    '#' => true,
-                    _ => false,
-                }
+                let index = (forward * x.0) as isize;
+                *self._map.get_wrapping(index, x.1 as isize) == '#'
             })
             .count()
     }
 }
 
-struct BoolMap {
-    _map: Vec<Vec<bool>>,
+/// The map as a grid of whether each square has a tree.
+pub struct BoolMap {
+    _map: Grid<bool>,
 }
 
 impl Map for BoolMap {
-    fn new(lines: &MapLines) -> Self {
-        let _map = lines
-            .iter()
-            .map(|line| {
-                line.chars()
-                    .map(|character| match character {
-                        '#' => true,
-                        _ => false,
-                    })
-                    .collect()
-            })
-            .collect();
+    fn new(map: &Grid<char>) -> Self {
+        let _map = map.map(|character| *character == '#');
 
         BoolMap { _map }
     }
 
     fn traverse(&self, forward: usize, down: usize) -> usize {
-        let vec_tuple = self._map.iter().step_by(down).enumerate();
+        let vec_tuple = (0..self._map.height()).step_by(down).enumerate();
 
         vec_tuple
             .filter(|x| {
-                let index = (forward * x.0) % x.1.len();
-                x.1[index]
+                let index = (forward * x.0) as isize;
+                *self._map.get_wrapping(index, x.1 as isize)
             })
             .count()
     }
 }
 
-struct BitMap {
-    width: u32,
-    _map: Vec<u32>,
+/// The map as one bit per square, each row in as many `u64`s as it takes.
+pub struct BitMap {
+    width: usize,
+    _map: Vec<Vec<u64>>,
 }
 
 impl Map for BitMap {
-    fn new(lines: &MapLines) -> Self {
-        let mut width: u32 = 0;
+    fn new(map: &Grid<char>) -> Self {
+        let width = map.width();
 
-        let _map = lines
-            .iter()
+        let _map = map
+            .rows()
             .map(|line| {
-                let mut row: u32 = 0;
-                width = line.len() as u32;
-
-                line.chars().enumerate().for_each(|x| {
-                    match x.1 {
-                        '#' => row = row | (1u32 << (width - (x.0 as u32))),
-                        _ => (),
-                    };
+                let mut row = vec![0u64; width.div_ceil(64)];
+
+                line.iter().enumerate().for_each(|x| {
+                    if *x.1 == '#' {
+                        row[x.0 / 64] |= 1u64 << (x.0 % 64);
+                    }
                 });
                 row
             })
@@ -110,72 +143,88 @@ impl Map for BitMap {
 
         vec_tuple
             .filter(|x| {
-                let index: u32 = ((forward * x.0) as u32) % self.width;
-                let bit = x.1 & (1u32 << (self.width - index));
+                let index = (forward * x.0) % self.width;
+                let bit = x.1[index / 64] & (1u64 << (index % 64));
 
-                match bit {
-                    0 => false,
-                    _ => true,
-                }
+                bit != 0
             })
             .count()
     }
 }
 
-fn part_1<T: Map>(map: &T) {
-    let trees_count = map.traverse(3, 1);
-
-    println!("\tTrees found: {}", trees_count);
+/// The trees hit going down `path`, e.g. `(3, 1)` for 3 right for every 1
+/// down.
+pub fn part_1<T: Map>(map: &T, path: (usize, usize)) -> usize {
+    map.traverse(path.0, path.1)
 }
 
-fn part_2<T: Map>(map: &T) {
-    let paths = vec![(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
-
-    let answer: usize = paths.iter().map(|x| map.traverse(x.0, x.1)).product();
-    println!("Answer: {}", answer);
+/// The product of the trees hit on each of `paths`.
+pub fn part_2<T: Map>(map: &T, paths: &[(usize, usize)]) -> usize {
+    paths.iter().map(|x| map.traverse(x.0, x.1)).product()
 }
 
-fn main() {
-    let lines = get_lines();
+/// Prints the answers for `input` using every kind of map.
+pub fn main(input: &str) -> Result<()> {
+    let map = get_map(input)?;
+    let default_map = DefaultMap::new(&map);
+    let bool_map = BoolMap::new(&map);
+    let bit_map = BitMap::new(&map);
+    let slopes = Slopes::default();
 
     println!("Part 1: \n ----------");
 
     println!("Default Map");
-    let mut start = Instant::now();
-
-    let default_map = DefaultMap::new(&lines);
-    part_1(&default_map);
-    println!("\tTime Taken: {:?}", start.elapsed());
+    println!("\tTrees found: {}", part_1(&default_map, slopes.part_one));
 
     println!("Bool Map");
-    start = Instant::now();
-
-    let bool_map = BoolMap::new(&lines);
-    part_1(&bool_map);
-    println!("\tTime Taken: {:?}", start.elapsed());
+    println!("\tTrees found: {}", part_1(&bool_map, slopes.part_one));
 
     println!("Bit Map");
-
-    start = Instant::now();
-
-    let bit_map = BitMap::new(&lines);
-    part_1(&bit_map);
-    println!("\tTime Taken: {:?}", start.elapsed());
+    println!("\tTrees found: {}", part_1(&bit_map, slopes.part_one));
 
     println!("---------- \nPart 2: \n----------");
 
     println!("Default Map");
-    start = Instant::now();
-    part_2(&default_map);
-    println!("\tTime Taken: {:?}", start.elapsed());
+    println!("Answer: {}", part_2(&default_map, &slopes.part_two));
 
     println!("Bool Map");
-    start = Instant::now();
-    part_2(&bool_map);
-    println!("\tTime Taken: {:?}", start.elapsed());
+    println!("Answer: {}", part_2(&bool_map, &slopes.part_two));
 
     println!("Bit Map");
-    start = Instant::now();
-    part_2(&bit_map);
-    println!("\tTime Taken: {:?}", start.elapsed());
+    println!("Answer: {}", part_2(&bit_map, &slopes.part_two));
+    Ok(())
+}
+
+#[test]
+fn test_maps() {
+    // Wider than a `u64`, with a tree wherever the column is a multiple of 7.
+    let row = |offset: usize| -> String {
+        (0..70)
+            .map(|x| {
+                if (x + offset).is_multiple_of(7) {
+                    '#'
+                } else {
+                    '.'
+                }
+            })
+            .collect()
+    };
+    let input: String = (0..50).map(|y| row(y) + "\n").collect();
+    let map = get_map(&input).unwrap();
+    let (default_map, bool_map, bit_map) =
+        (DefaultMap::new(&map), BoolMap::new(&map), BitMap::new(&map));
+    for (forward, down) in [(1, 1), (3, 1), (6, 1), (7, 1), (1, 2), (71, 3)] {
+        let trees = default_map.traverse(forward, down);
+        assert_eq!(trees, bool_map.traverse(forward, down));
+        assert_eq!(trees, bit_map.traverse(forward, down));
+    }
+    assert_eq!(50, bit_map.traverse(6, 1));
+
+    for input in ["", "\n"] {
+        let e = get_map(input).unwrap_err();
+        assert_eq!(
+            (1, 1, "a row of `.` or `#`"),
+            (e.line, e.column, e.expected.as_str())
+        );
+    }
 }
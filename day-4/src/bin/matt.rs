@@ -0,0 +1,6 @@
+use aoc_common::Solution;
+use day_4::matt::{self, Matt};
+
+fn main() {
+    aoc_common::input::run_with_input(Solution::new::<Matt>(4, "matt"), matt::main)
+}
@@ -0,0 +1,149 @@
+// Random passport batches, with a controlled share of invalid passports.
+//
+// A passport is either valid, missing a required field (which fails both
+// parts), or has every field but one bad value (which only fails part two).
+use aoc_common::generate::{Generated, Rng};
+
+const REQUIRED: [&str; 7] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+const EYE_COLORS: [&str; 7] = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+const HEX: &[u8] = b"0123456789abcdef";
+
+/// How a batch of passports should be made up.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Batch {
+    pub passports: usize,
+    /// The chance of a passport being valid.
+    pub valid: f64,
+    /// The chance of a passport missing a required field. The rest have a
+    /// bad value.
+    pub missing: f64,
+}
+
+/// `size` passports, half of them valid and a quarter missing a field.
+pub fn input(rng: &mut Rng, size: usize) -> Generated {
+    batch(
+        rng,
+        &Batch {
+            passports: size,
+            valid: 0.5,
+            missing: 0.25,
+        },
+    )
+}
+
+/// A batch of passports made up like `batch`.
+pub fn batch(rng: &mut Rng, batch: &Batch) -> Generated {
+    let mut passports = vec![];
+    let (mut complete, mut valid) = (0, 0);
+    for _ in 0..batch.passports {
+        let mut fields: Vec<(&str, String)> = REQUIRED
+            .iter()
+            .map(|&field| (field, good_value(rng, field)))
+            .collect();
+        if rng.chance(0.5) {
+            fields.push(("cid", rng.range(100..=350u32).to_string()));
+        }
+
+        let roll = rng.fraction();
+        if roll < batch.valid {
+            complete += 1;
+            valid += 1;
+        } else if roll < batch.valid + batch.missing {
+            let missing = *rng.choose(&REQUIRED);
+            fields.retain(|(field, _)| *field != missing);
+        } else {
+            complete += 1;
+            let i = rng.index(REQUIRED.len());
+            fields[i].1 = bad_value(rng, REQUIRED[i]);
+        }
+
+        rng.shuffle(&mut fields);
+        let mut passport = String::new();
+        for (i, (field, value)) in fields.iter().enumerate() {
+            if i > 0 {
+                passport.push(if rng.chance(0.3) { '\n' } else { ' ' });
+            }
+            passport.push_str(&format!("{}:{}", field, value));
+        }
+        passport.push('\n');
+        passports.push(passport);
+    }
+    Generated::new(passports.join("\n"), complete, valid)
+}
+
+fn good_value(rng: &mut Rng, field: &str) -> String {
+    match field {
+        "byr" => rng.range(1920..=2002u32).to_string(),
+        "iyr" => rng.range(2010..=2020u32).to_string(),
+        "eyr" => rng.range(2020..=2030u32).to_string(),
+        "hgt" if rng.chance(0.5) => format!("{}cm", rng.range(150..=193u32)),
+        "hgt" => format!("{}in", rng.range(59..=76u32)),
+        "hcl" => format!("#{}", hex(rng, 6)),
+        "ecl" => rng.choose(&EYE_COLORS).to_string(),
+        "pid" => digits(rng, 9),
+        _ => unreachable!("not a required field: {}", field),
+    }
+}
+
+fn bad_value(rng: &mut Rng, field: &str) -> String {
+    let choice = rng.range(0..=2u8);
+    match (field, choice) {
+        ("byr", 0) => rng.range(1900..=1919u32).to_string(),
+        ("byr", _) => rng.range(2003..=2020u32).to_string(),
+        ("iyr", 0) => rng.range(2000..=2009u32).to_string(),
+        ("iyr", _) => rng.range(2021..=2030u32).to_string(),
+        ("eyr", 0) => rng.range(2010..=2019u32).to_string(),
+        ("eyr", _) => rng.range(2031..=2040u32).to_string(),
+        ("hgt", 0) => format!("{}cm", rng.range(100..=149u32)),
+        ("hgt", 1) => format!("{}in", rng.range(77..=99u32)),
+        ("hgt", _) => rng.range(59..=193u32).to_string(),
+        ("hcl", 0) => {
+            let length = *rng.choose(&[3, 5, 7]);
+            format!("#{}", hex(rng, length))
+        }
+        ("hcl", 1) => format!("#{}z", hex(rng, 5)),
+        ("hcl", _) => hex(rng, 6),
+        ("ecl", 0) => "xry".to_string(),
+        ("ecl", _) => rng.choose(&["gmt", "zzz", "blue", "am"]).to_string(),
+        ("pid", 0) => digits(rng, 8),
+        ("pid", 1) => digits(rng, 10),
+        ("pid", _) => format!("{}x", digits(rng, 8)),
+        _ => unreachable!("not a required field: {}", field),
+    }
+}
+
+fn hex(rng: &mut Rng, length: usize) -> String {
+    (0..length).map(|_| *rng.choose(HEX) as char).collect()
+}
+
+fn digits(rng: &mut Rng, length: usize) -> String {
+    (0..length)
+        .map(|_| (b'0' + rng.range(0..=9u8)) as char)
+        .collect()
+}
+
+#[test]
+fn test_generate() {
+    use aoc_common::{Answer, Solver};
+
+    use crate::matt::Matt;
+
+    for seed in 0..20 {
+        let generated = input(&mut Rng::new(seed), 100);
+        let passports = Matt::parse(&generated.input).unwrap();
+        assert_eq!(100, passports.passports.len());
+        assert_eq!(generated.one, Matt::part_one(&passports));
+        assert_eq!(generated.two, Matt::part_two(&passports));
+    }
+
+    let all_valid = Batch {
+        passports: 10,
+        valid: 1.0,
+        missing: 0.0,
+    };
+    let generated = batch(&mut Rng::new(0), &all_valid);
+    assert_eq!(
+        (Answer::Int(10), Answer::Int(10)),
+        (generated.one, generated.two)
+    );
+}
@@ -0,0 +1,97 @@
+// --- Day 4: Passport Processing ---
+//
+// https://adventofcode.com/2020/day/4
+use std::ops::RangeInclusive;
+
+use aoc_common::params::{Parameter, Params};
+use aoc_common::{Result, Solution};
+
+pub mod generate;
+pub mod matt;
+#[cfg(feature = "vickz84259")]
+pub mod vickz84259;
+
+/// The puzzle's constants, which every author's solution takes.
+pub const PARAMETERS: &[Parameter] = &[
+    Parameter {
+        name: "byr",
+        default: "1920-2002",
+        about: "valid birth years",
+    },
+    Parameter {
+        name: "iyr",
+        default: "2010-2020",
+        about: "valid issue years",
+    },
+    Parameter {
+        name: "eyr",
+        default: "2020-2030",
+        about: "valid expiration years",
+    },
+    Parameter {
+        name: "hgt-cm",
+        default: "150-193",
+        about: "valid heights in centimetres",
+    },
+    Parameter {
+        name: "hgt-in",
+        default: "59-76",
+        about: "valid heights in inches",
+    },
+];
+
+/// The values that the fields checked against a range may take.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ranges {
+    pub birth_year: RangeInclusive<u32>,
+    pub issue_year: RangeInclusive<u32>,
+    pub expiration_year: RangeInclusive<u32>,
+    pub height_cm: RangeInclusive<u32>,
+    pub height_in: RangeInclusive<u32>,
+}
+
+impl Ranges {
+    /// The ranges in `params`, which has a value for each of [`PARAMETERS`].
+    pub fn from_params(params: &Params) -> Result<Ranges> {
+        let range = |name| params.get_with(name, "a range like 1920-2002", range);
+        Ok(Ranges {
+            birth_year: range("byr")?,
+            issue_year: range("iyr")?,
+            expiration_year: range("eyr")?,
+            height_cm: range("hgt-cm")?,
+            height_in: range("hgt-in")?,
+        })
+    }
+}
+
+impl Default for Ranges {
+    /// The puzzle's ranges.
+    fn default() -> Ranges {
+        Ranges::from_params(&Params::defaults(PARAMETERS)).unwrap()
+    }
+}
+
+fn range(value: &str) -> Option<RangeInclusive<u32>> {
+    let (start, end) = value.split_once('-')?;
+    let (start, end) = (start.parse().ok()?, end.parse().ok()?);
+    if start <= end {
+        Some(start..=end)
+    } else {
+        None
+    }
+}
+
+/// The batch of passports, with the ranges to check them against.
+pub struct Passports<P> {
+    pub passports: Vec<P>,
+    pub ranges: Ranges,
+}
+
+/// Every author's solution for the day.
+pub fn solutions() -> Vec<Solution> {
+    vec![
+        Solution::new::<matt::Matt>(4, "matt"),
+        #[cfg(feature = "vickz84259")]
+        Solution::new::<vickz84259::Vickz84259>(4, "vickz84259"),
+    ]
+}
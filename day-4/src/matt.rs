@@ -2,11 +2,50 @@
 //
 // https://adventofcode.com/2020/day/4
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{self, BufRead};
 
+use aoc_common::params::{Parameter, Params};
+use aoc_common::records::records;
+use aoc_common::{parse, Answer, ParseError, Result, Solver};
+
+use crate::{Passports, Ranges};
+
+/// Matt's solution.
+pub struct Matt;
+
+impl Solver for Matt {
+    type Input = Passports<Passport>;
+
+    const PARAMETERS: &'static [Parameter] = crate::PARAMETERS;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Self::parse_with(input, &Params::defaults(Self::PARAMETERS))
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Input> {
+        let ranges = Ranges::from_params(params)?;
+        let mut passports = vec![];
+        for record in records(input.as_bytes()) {
+            let passport = record?.parse(|text| {
+                let lines: Vec<_> = text.lines().collect();
+                Passport::from_seq(text, &lines)
+            })?;
+            passports.push(passport);
+        }
+        Ok(Passports { passports, ranges })
+    }
+
+    fn part_one(input: &Self::Input) -> Answer {
+        part_one(&input.passports).into()
+    }
+
+    fn part_two(input: &Self::Input) -> Answer {
+        part_two(&input.passports, &input.ranges).into()
+    }
+}
+
+/// A passport field, by its three-letter key.
 #[derive(Debug, PartialEq, Eq, Hash)]
-enum Field {
+pub enum Field {
     Byr,
     Iyr,
     Hgt,
@@ -16,28 +55,28 @@ enum Field {
     Eyr,
     Cid,
 }
+/// The fields of a passport, and their values.
 #[derive(Debug)]
-struct Passport(HashMap<Field, String>);
+pub struct Passport(pub HashMap<Field, String>);
+
 impl Passport {
-    fn is_valid(&self) -> bool {
+    /// Whether every field but `cid` is present.
+    pub fn is_valid(&self) -> bool {
         self.0.len() == 8 || (self.0.len() == 7 && !self.0.contains_key(&Field::Cid))
     }
 
-    fn is_valid_strict(&self) -> bool {
-        self.is_valid() && {
-            self.0
-                .iter()
-                .map(|fv| is_field_valid(fv.0, fv.1))
-                .fold(true, |acc, x| acc && x)
-        }
+    /// Whether every field but `cid` is present, and every field valid.
+    pub fn is_valid_strict(&self, ranges: &Ranges) -> bool {
+        self.is_valid() && { self.0.iter().all(|fv| is_field_valid(fv.0, fv.1, ranges)) }
     }
 
-    fn from_seq(seq: &Vec<String>) -> Passport {
+    /// Builds a passport from its lines, which must be slices of `input`.
+    pub fn from_seq(input: &str, seq: &[&str]) -> Result<Passport, ParseError> {
         let mut pass = HashMap::new();
         for line in seq {
-            for part in line.split(' ') {
-                let kv: Vec<_> = part.splitn(2, ':').collect();
-                let f = match kv[0] {
+            for part in line.split_whitespace() {
+                let (key, val) = parse::split_once(input, part, ":", "`key:value`")?;
+                let f = match key {
                     "byr" => Field::Byr,
                     "iyr" => Field::Iyr,
                     "eyr" => Field::Eyr,
@@ -46,48 +85,49 @@ impl Passport {
                     "ecl" => Field::Ecl,
                     "pid" => Field::Pid,
                     "cid" => Field::Cid,
-                    _ => unreachable!(),
+                    _ => return Err(ParseError::at(input, key, "a passport field")),
                 };
-                pass.insert(f, kv[1].to_string());
+                pass.insert(f, val.to_string());
             }
         }
-        Passport(pass)
+        Ok(Passport(pass))
     }
 }
 
-fn is_field_valid(f: &Field, val: &str) -> bool {
+/// Whether `val` is a valid value of the field `f`.
+pub fn is_field_valid(f: &Field, val: &str, ranges: &Ranges) -> bool {
     match f {
         Field::Byr => {
-            if let Ok(num) = val.parse::<i32>() {
-                num >= 1920 && num <= 2002
+            if let Ok(num) = val.parse::<u32>() {
+                ranges.birth_year.contains(&num)
             } else {
                 false
             }
         }
         Field::Iyr => {
-            if let Ok(num) = val.parse::<i32>() {
-                num >= 2010 && num <= 2020
+            if let Ok(num) = val.parse::<u32>() {
+                ranges.issue_year.contains(&num)
             } else {
                 false
             }
         }
         Field::Eyr => {
-            if let Ok(num) = val.parse::<i32>() {
-                num >= 2020 && num <= 2030
+            if let Ok(num) = val.parse::<u32>() {
+                ranges.expiration_year.contains(&num)
             } else {
                 false
             }
         }
         Field::Hgt => {
             if val.ends_with("cm") {
-                if let Ok(num) = val.trim_end_matches("cm").parse::<i32>() {
-                    num >= 150 && num <= 193
+                if let Ok(num) = val.trim_end_matches("cm").parse::<u32>() {
+                    ranges.height_cm.contains(&num)
                 } else {
                     false
                 }
             } else if val.ends_with("in") {
-                if let Ok(num) = val.trim_end_matches("in").parse::<i32>() {
-                    num >= 59 && num <= 76
+                if let Ok(num) = val.trim_end_matches("in").parse::<u32>() {
+                    ranges.height_in.contains(&num)
                 } else {
                     false
                 }
@@ -97,74 +137,73 @@ fn is_field_valid(f: &Field, val: &str) -> bool {
         }
         Field::Hcl => {
             val.len() == 7
-                && val.chars().nth(0).unwrap() == '#'
+                && val.starts_with('#')
                 && val
                     .chars()
                     .skip(1)
-                    .map(|c| c.is_digit(10) || ['a', 'b', 'c', 'd', 'e', 'f'].contains(&c))
-                    .fold(true, |acc, x| acc && x)
+                    .all(|c| c.is_ascii_digit() || ['a', 'b', 'c', 'd', 'e', 'f'].contains(&c))
         }
         Field::Ecl => ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"].contains(&val),
-        Field::Pid => {
-            val.len() == 9
-                && val
-                    .chars()
-                    .map(|c| c.is_digit(10))
-                    .fold(true, |acc, x| acc && x)
-        }
+        Field::Pid => val.len() == 9 && val.chars().all(|c| c.is_ascii_digit()),
         Field::Cid => true,
     }
 }
 
-fn main() {
-    let passports = input("src/matt.txt").unwrap();
-
-    println!(
-        "Part One: {} ",
-        passports.iter().filter(|x| x.is_valid()).count(),
-    );
-    println!(
-        "Part Two: {} ",
-        passports.iter().filter(|x| x.is_valid_strict()).count(),
-    );
+/// How many passports have every required field.
+pub fn part_one(passports: &[Passport]) -> usize {
+    passports.iter().filter(|x| x.is_valid()).count()
 }
 
-fn input(fname: &str) -> io::Result<Vec<Passport>> {
-    let mut input = vec![];
-    let file = File::open(fname)?;
-    let buf = io::BufReader::new(file);
-    let mut p = vec![];
-    for item in buf.lines() {
-        let line = item?;
-        if line.is_empty() {
-            input.push(Passport::from_seq(&p));
-            p.clear();
-        } else {
-            p.push(line);
-        }
-    }
-    input.push(Passport::from_seq(&p));
-    Ok(input)
+/// How many passports have every required field, all of them valid.
+pub fn part_two(passports: &[Passport], ranges: &Ranges) -> usize {
+    passports
+        .iter()
+        .filter(|x| x.is_valid_strict(ranges))
+        .count()
 }
 
-#[test]
-fn test_matt() -> io::Result<()> {
-    let valid = input("src/matt_test.txt")?
-        .iter()
-        .filter(|x| x.is_valid())
-        .count();
-    assert_eq!(valid, 2);
+/// Prints the answers for `input`.
+pub fn main(input: &str) -> Result<()> {
+    let Passports { passports, ranges } = Matt::parse(input)?;
 
+    println!("Part One: {} ", part_one(&passports));
+    println!("Part Two: {} ", part_two(&passports, &ranges));
     Ok(())
 }
 
 #[test]
-fn test_valid() -> io::Result<()> {
-    let valid = input("src/matt_test_valid.txt")?
-        .iter()
-        .filter(|x| x.is_valid_strict())
-        .count();
-    assert_eq!(valid, 4);
+fn test_parse_error() {
+    let e = Matt::parse("byr:1937 iyr:2017\n\necl:gry\nhgt:183cm xyz:1")
+        .err()
+        .unwrap();
+    assert_eq!(
+        "invalid input: line 4, column 11: expected a passport field, found `xyz`\n  |\n4 | hgt:183cm xyz:1\n  |           ^^^",
+        e.to_string()
+    );
+}
 
-    Ok(())
+#[test]
+fn test_ranges() {
+    let passport = "byr:1900 iyr:2015 eyr:2025 hgt:60in hcl:#123abc ecl:brn pid:000000001";
+    assert_eq!(
+        Answer::Int(0),
+        Matt::part_two(&Matt::parse(passport).unwrap())
+    );
+
+    let params = Params::from_args(&["byr=1900-2000"])
+        .unwrap()
+        .resolve(Matt::PARAMETERS)
+        .unwrap();
+    let passports = Matt::parse_with(passport, &params).unwrap();
+    assert_eq!(Answer::Int(1), Matt::part_two(&passports));
+
+    let params = Params::from_args(&["hgt-in=80-70"])
+        .unwrap()
+        .resolve(Matt::PARAMETERS)
+        .unwrap();
+    let e = Matt::parse_with(passport, &params).err().unwrap();
+    assert_eq!(
+        "invalid parameter: `hgt-in=80-70`, expected a range like 1920-2002",
+        e.to_string()
+    );
 }
@@ -1,18 +1,51 @@
-use std::fmt;
-use std::fs::File;
-use std::io::{self, BufRead};
+use std::ops::RangeInclusive;
 use std::str::FromStr;
 
+use aoc_common::params::{Parameter, Params};
+use aoc_common::records::records;
+use aoc_common::{Answer, ParseError, Result, Solver};
 use itertools::Itertools;
 
+use crate::{Passports, Ranges};
+
+/// vickz84259's solution.
+pub struct Vickz84259;
+
+impl Solver for Vickz84259 {
+    type Input = Passports<Passport>;
+
+    const PARAMETERS: &'static [Parameter] = crate::PARAMETERS;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Self::parse_with(input, &Params::defaults(Self::PARAMETERS))
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Input> {
+        Ok(Passports {
+            passports: get_passports(input)?,
+            ranges: Ranges::from_params(params)?,
+        })
+    }
+
+    fn part_one(input: &Self::Input) -> Answer {
+        part_1(&input.passports).into()
+    }
+
+    fn part_two(input: &Self::Input) -> Answer {
+        part_2(&input.passports, &input.ranges).into()
+    }
+}
+
+/// A field's value: a number if it is one, text otherwise.
 #[derive(Debug)]
-enum Entry {
+pub enum Entry {
     StrVal(String),
     IntVal(u32),
 }
 
+/// A passport's fields, any of which may be missing.
 #[derive(Default, Debug)]
-struct Passport {
+pub struct Passport {
     birth_year: Option<Entry>,
     issue_year: Option<Entry>,
     exp_year: Option<Entry>,
@@ -23,21 +56,13 @@ struct Passport {
     cid: Option<Entry>,
 }
 
-#[derive(Debug)]
-struct PassParseError;
-
-impl fmt::Display for PassParseError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str("Invalid Passport String")
-    }
-}
-
 impl Passport {
-    fn new() -> Self {
+    pub fn new() -> Self {
         Default::default()
     }
 
-    fn is_valid(&self) -> bool {
+    /// Whether every field but `cid` is present.
+    pub fn is_valid(&self) -> bool {
         // Checks whether all entries exist
 
         let test = self
@@ -57,27 +82,31 @@ impl Passport {
         value >= lower && value <= higher
     }
 
-    fn validate_limits(value: &Option<Entry>, lower: u32, higher: u32) -> bool {
+    fn validate_range(value: u32, range: &RangeInclusive<u32>) -> bool {
+        Passport::validate_ints(value, *range.start(), *range.end())
+    }
+
+    fn validate_limits(value: &Option<Entry>, range: &RangeInclusive<u32>) -> bool {
         use Entry::IntVal;
         match value {
-            Some(IntVal(value)) => Passport::validate_ints(*value, lower, higher),
+            Some(IntVal(value)) => Passport::validate_range(*value, range),
             _ => false,
         }
     }
 
-    fn validate_height(value: &Option<Entry>) -> bool {
+    fn validate_height(value: &Option<Entry>, ranges: &Ranges) -> bool {
         use Entry::StrVal;
         match value {
             Some(StrVal(value_str)) => {
-                let index = value_str.chars().count() - 2;
-                let slice = &value_str[..index];
+                let (slice, range) =
+                    match (value_str.strip_suffix("cm"), value_str.strip_suffix("in")) {
+                        (Some(slice), _) => (slice, &ranges.height_cm),
+                        (_, Some(slice)) => (slice, &ranges.height_in),
+                        _ => return false,
+                    };
 
                 match slice.parse::<u32>() {
-                    Ok(height) => match &value_str[index..] {
-                        "cm" => Passport::validate_ints(height, 150, 193),
-                        "in" => Passport::validate_ints(height, 59, 76),
-                        _ => false,
-                    },
+                    Ok(height) => Passport::validate_range(height, range),
                     Err(_) => false,
                 }
             }
@@ -89,11 +118,10 @@ impl Passport {
         use Entry::StrVal;
         match value {
             Some(StrVal(value_str)) => {
-                value_str.starts_with("#")
+                value_str.starts_with('#')
                     && value_str[1..]
                         .chars()
-                        .map(|c| "0123456789abcdef".contains(c))
-                        .all(|x| x)
+                        .all(|c| "0123456789abcdef".contains(c))
             }
             _ => false,
         }
@@ -104,7 +132,7 @@ impl Passport {
         match value {
             Some(StrVal(value_str)) => {
                 let slice = &value_str[..];
-                vec!["amb", "blu", "brn", "gry", "grn", "hzl", "oth"].contains(&slice)
+                ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"].contains(&slice)
             }
             _ => false,
         }
@@ -121,12 +149,14 @@ impl Passport {
         }
     }
 
-    fn validate(&self) -> bool {
-        vec![
-            Passport::validate_limits(&self.birth_year, 1920, 2002),
-            Passport::validate_limits(&self.issue_year, 2010, 2020),
-            Passport::validate_limits(&self.exp_year, 2020, 2030),
-            Passport::validate_height(&self.height),
+    /// Whether every field but `cid` is present and valid, with years and
+    /// heights in `ranges`. Hair colours of any length are taken to be valid.
+    pub fn validate(&self, ranges: &Ranges) -> bool {
+        [
+            Passport::validate_limits(&self.birth_year, &ranges.birth_year),
+            Passport::validate_limits(&self.issue_year, &ranges.issue_year),
+            Passport::validate_limits(&self.exp_year, &ranges.expiration_year),
+            Passport::validate_height(&self.height, ranges),
             Passport::validate_hair_color(&self.hair_color),
             Passport::validate_eye_color(&self.eye_color),
             Passport::validate_id(&self.pid),
@@ -137,25 +167,20 @@ impl Passport {
 }
 
 impl FromStr for Passport {
-    type Err = PassParseError;
-
-    fn from_str(s: &str) -> Result<Passport, PassParseError> {
-        let pass_str: String = s.split('\n').intersperse(" ").collect();
+    type Err = ParseError;
 
-        let fields = pass_str.split_whitespace().map(|field| {
+    fn from_str(s: &str) -> Result<Passport, ParseError> {
+        let fields = s.split_whitespace().map(|field| {
             field
                 .split(":")
                 .collect_tuple::<(&str, &str)>()
-                .ok_or(PassParseError)
+                .ok_or_else(|| ParseError::at(s, field, "`key:value`"))
         });
 
         let mut passport = Passport::new();
 
         for field in fields {
-            let field = match field {
-                Ok(field) => field,
-                Err(e) => return Err(e),
-            };
+            let field = field?;
 
             let entry = field.1.parse::<u32>().map_or_else(
                 |_| Some(Entry::StrVal(field.1.to_string())),
@@ -171,65 +196,64 @@ impl FromStr for Passport {
                 "ecl" => passport.eye_color = entry,
                 "pid" => passport.pid = Some(Entry::StrVal(field.1.to_string())),
                 "cid" => passport.cid = entry,
-                _ => return Err(PassParseError),
+                _ => return Err(ParseError::at(s, field.0, "a passport field")),
             }
         }
         Ok(passport)
     }
 }
 
-fn get_passports() -> Vec<Passport> {
-    let file = File::open("vickz84259.txt").expect("Unable to read file");
-    let mut reader = io::BufReader::new(file);
-
+/// Parses the batch of passports, which are separated by blank lines.
+pub fn get_passports(input: &str) -> Result<Vec<Passport>> {
     let mut vector: Vec<Passport> = Vec::new();
-    let mut buf_1 = String::new();
-    let mut buf_2 = String::new();
-
-    loop {
-        let no_of_bytes = reader.read_line(&mut buf_1).unwrap_or(0);
-
-        buf_2.push_str(&buf_1);
-
-        if &buf_1 == "\n" || &buf_1 == "" {
-            vector.push(buf_2.parse().unwrap());
-            buf_2.clear();
-        }
-
-        buf_1.clear();
 
-        if no_of_bytes == 0 {
-            break;
-        }
+    for record in records(input.as_bytes()) {
+        vector.push(record?.parse(str::parse)?);
     }
-    vector
+    Ok(vector)
 }
 
-fn part_1(passports: &Vec<Passport>) {
-    let valid_count = passports
+/// How many passports have every required field.
+pub fn part_1(passports: &[Passport]) -> usize {
+    passports
         .iter()
         .filter(|passport| passport.is_valid())
-        .count();
-
-    println!("Valid passports: {}", valid_count);
+        .count()
 }
 
-fn part_2(passports: &Vec<Passport>) {
-    let valid_count = passports
+/// How many passports have every required field, all of them valid.
+pub fn part_2(passports: &[Passport], ranges: &Ranges) -> usize {
+    passports
         .iter()
-        .filter(|passport| passport.validate())
-        .count();
-
-    println!("Valid passports: {}", valid_count);
+        .filter(|passport| passport.validate(ranges))
+        .count()
 }
 
-fn main() {
-    let passports = get_passports();
+/// Prints the answers for `input`.
+pub fn main(input: &str) -> Result<()> {
+    let Passports { passports, ranges } = Vickz84259::parse(input)?;
 
     println!("Part 1: \n----------");
-    part_1(&passports);
+    println!("Valid passports: {}", part_1(&passports));
 
     println!("----------");
     println!("Part 2: \n----------");
-    part_2(&passports);
+    println!("Valid passports: {}", part_2(&passports, &ranges));
+    Ok(())
+}
+
+#[test]
+fn test_height() {
+    let valid = |height: &str| {
+        let passport = format!(
+            "byr:1980 iyr:2015 eyr:2025 hgt:{} hcl:#123abc ecl:brn pid:000000001\n",
+            height
+        );
+        Vickz84259::part_two(&Vickz84259::parse(&passport).unwrap())
+    };
+    assert_eq!(Answer::Int(1), valid("183cm"));
+    assert_eq!(Answer::Int(1), valid("60in"));
+    for height in ["x", "cm", "183", "200cm", "60", "é3cm", "18é"] {
+        assert_eq!(Answer::Int(0), valid(height), "{}", height);
+    }
 }
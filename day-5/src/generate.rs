@@ -0,0 +1,56 @@
+// Random boarding pass lists: every seat from somewhere near the front of the
+// plane to somewhere near the back, except one.
+//
+// The plane always has 128 rows of 8 seats, so there's no size to choose.
+use aoc_common::generate::{Generated, Rng};
+
+/// The passes of a full flight, in a random order, with one seat missing.
+pub fn input(rng: &mut Rng, _size: usize) -> Generated {
+    let first = rng.range(16..=96usize);
+    let last = rng.range(900..=1000usize);
+    flight(rng, first, last)
+}
+
+/// The passes for every seat from `first` to `last`, in a random order, but
+/// for one seat in between.
+pub fn flight(rng: &mut Rng, first: usize, last: usize) -> Generated {
+    assert!(first + 2 <= last && last < 1024, "no room for your seat");
+    let yours = rng.range(first + 1..=last - 1);
+
+    let mut seats: Vec<_> = (first..=last).filter(|&seat| seat != yours).collect();
+    rng.shuffle(&mut seats);
+    let input = seats
+        .iter()
+        .map(|&seat| format!("{}\n", boarding_pass(seat)))
+        .collect();
+    Generated::new(input, last, yours)
+}
+
+/// The boarding pass for a seat ID.
+pub fn boarding_pass(seat: usize) -> String {
+    let (row, column) = (seat / 8, seat % 8);
+    let bits = |value: usize, count: usize, zero: char, one: char| {
+        (0..count)
+            .rev()
+            .map(|bit| if value >> bit & 1 == 0 { zero } else { one })
+            .collect::<String>()
+    };
+    bits(row, 7, 'F', 'B') + &bits(column, 3, 'L', 'R')
+}
+
+#[test]
+fn test_generate() {
+    use aoc_common::Solver;
+
+    use crate::matt::Matt;
+
+    assert_eq!("BFFFBBFRRR", boarding_pass(567));
+    assert_eq!("BBFFBBFRLL", boarding_pass(820));
+
+    for seed in 0..20 {
+        let generated = input(&mut Rng::new(seed), 0);
+        let passes = Matt::parse(&generated.input).unwrap();
+        assert_eq!(generated.one, Matt::part_one(&passes));
+        assert_eq!(generated.two, Matt::part_two(&passes));
+    }
+}
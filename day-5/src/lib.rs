@@ -0,0 +1,78 @@
+// --- Day 5: Binary Boarding ---
+//
+// https://adventofcode.com/2020/day/5
+use aoc_common::params::{Parameter, Params};
+use aoc_common::{Result, Solution};
+
+pub mod generate;
+pub mod matt;
+#[cfg(feature = "vickz84259")]
+pub mod vickz84259;
+
+/// The puzzle's constants, which every author's solution takes.
+pub const PARAMETERS: &[Parameter] = &[
+    Parameter {
+        name: "rows",
+        default: "128",
+        about: "rows in the plane, a power of two",
+    },
+    Parameter {
+        name: "columns",
+        default: "8",
+        about: "seats in each row, a power of two",
+    },
+];
+
+/// The size of the plane, which decides how long boarding passes are and how
+/// seat IDs are numbered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Plane {
+    pub rows: usize,
+    pub columns: usize,
+}
+
+impl Plane {
+    /// The plane in `params`, which has a value for each of [`PARAMETERS`].
+    pub fn from_params(params: &Params) -> Result<Plane> {
+        let size = |name| {
+            params.get_with(name, "a power of two", |value| {
+                value.parse().ok().filter(|n: &usize| n.is_power_of_two())
+            })
+        };
+        Ok(Plane {
+            rows: size("rows")?,
+            columns: size("columns")?,
+        })
+    }
+
+    /// How many of a boarding pass's characters choose the row.
+    pub fn row_characters(&self) -> usize {
+        self.rows.trailing_zeros() as usize
+    }
+
+    /// How many of a boarding pass's characters choose the column.
+    pub fn column_characters(&self) -> usize {
+        self.columns.trailing_zeros() as usize
+    }
+
+    /// The ID of the seat at `row` and `column`.
+    pub fn seat_id(&self, row: usize, column: usize) -> usize {
+        row * self.columns + column
+    }
+}
+
+impl Default for Plane {
+    /// The puzzle's plane.
+    fn default() -> Plane {
+        Plane::from_params(&Params::defaults(PARAMETERS)).unwrap()
+    }
+}
+
+/// Every author's solution for the day.
+pub fn solutions() -> Vec<Solution> {
+    vec![
+        Solution::new::<matt::Matt>(5, "matt"),
+        #[cfg(feature = "vickz84259")]
+        Solution::new::<vickz84259::Vickz84259>(5, "vickz84259"),
+    ]
+}
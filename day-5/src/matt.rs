@@ -1,82 +1,120 @@
 // --- Day 5: Binary Boarding ---
 //
 // https://adventofcode.com/2020/day/5
-use std::fs::File;
-use std::io::{self, BufRead};
+use aoc_common::params::{Parameter, Params};
+use aoc_common::{parse, Answer, ParseError, Result, Solver};
 
+use crate::Plane;
+
+/// Matt's solution.
+pub struct Matt;
+
+impl Solver for Matt {
+    type Input = Vec<BoardingPass>;
+
+    const PARAMETERS: &'static [Parameter] = crate::PARAMETERS;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Self::parse_with(input, &Params::defaults(Self::PARAMETERS))
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Input> {
+        let plane = Plane::from_params(params)?;
+        Ok(parse::lines(input, |line| {
+            parse_boarding_pass(line, &plane)
+        })?)
+    }
+
+    fn part_one(passes: &Self::Input) -> Answer {
+        part_one(passes).map_or(Answer::Unsolved, Answer::from)
+    }
+
+    fn part_two(passes: &Self::Input) -> Answer {
+        part_two(passes).map_or(Answer::Unsolved, Answer::from)
+    }
+}
+
+/// The seat a boarding pass is for.
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
-struct BoardingPass {
-    row: usize,
-    col: usize,
-    seat_id: usize,
+pub struct BoardingPass {
+    pub row: usize,
+    pub col: usize,
+    /// The row times the seats in a row, plus the column.
+    pub seat_id: usize,
 }
 
 impl BoardingPass {
-    fn new(row: usize, col: usize) -> BoardingPass {
+    pub fn new(row: usize, col: usize, plane: &Plane) -> BoardingPass {
         BoardingPass {
             row,
             col,
-            seat_id: row * 8 + col,
+            seat_id: plane.seat_id(row, col),
         }
     }
 }
 
-fn main() {
-    let passes = input("src/matt.txt").unwrap();
+/// Prints the answers for `input`.
+pub fn main(input: &str) -> Result<()> {
+    let passes = Matt::parse(input)?;
 
-    println!("Part One: {} ", part_one(&passes));
-    println!("Part Two: {} ", part_two(&passes));
+    match part_one(&passes) {
+        Some(seat_id) => println!("Part One: {} ", seat_id),
+        None => println!("Part One: no boarding passes"),
+    }
+    match part_two(&passes) {
+        Some(seat_id) => println!("Part Two: {} ", seat_id),
+        None => println!("Part Two: no seat missing"),
+    }
+    Ok(())
 }
 
-fn part_one(xs: &Vec<BoardingPass>) -> usize {
-    xs.iter().map(|x| x.seat_id).max().unwrap()
+/// The highest seat ID on any pass, if there are any.
+pub fn part_one(xs: &[BoardingPass]) -> Option<usize> {
+    xs.iter().map(|x| x.seat_id).max()
 }
-fn part_two(xs: &Vec<BoardingPass>) -> usize {
+
+/// The first seat ID missing between the lowest and highest on the passes,
+/// if any is.
+pub fn part_two(xs: &[BoardingPass]) -> Option<usize> {
     let mut ids: Vec<_> = xs.iter().map(|x| x.seat_id).collect();
     ids.sort();
-    let min = *ids.iter().min().unwrap();
-    let max = *ids.iter().max().unwrap();
-    for idx in min..=max {
-        if !ids.contains(&idx) {
-            return idx;
-        }
-    }
-    0
+    let min = *ids.iter().min()?;
+    let max = *ids.iter().max()?;
+    (min..=max).find(|idx| !ids.contains(idx))
 }
-fn parse_boarding_pass(s: &str) -> BoardingPass {
-    let (row, col) = s.split_at(s.len() - 3);
-    let row_binstr = row
-        .chars()
-        .map(|x| match x {
-            'F' => '0',
-            'B' => '1',
-            _ => unreachable!(),
-        })
-        .collect::<String>();
-    let col_binstr = col
-        .chars()
-        .map(|x| match x {
-            'R' => '1',
-            'L' => '0',
-            _ => unreachable!(),
-        })
-        .collect::<String>();
-    let row = usize::from_str_radix(&row_binstr, 2).unwrap();
-    let col = usize::from_str_radix(&col_binstr, 2).unwrap();
-    BoardingPass::new(row, col)
+
+/// Parses a boarding pass like `FBFBBFFRLR` for a seat on `plane`.
+pub fn parse_boarding_pass(s: &str, plane: &Plane) -> Result<BoardingPass, ParseError> {
+    let (rows, cols) = (plane.row_characters(), plane.column_characters());
+    if s.len() != rows + cols || !s.is_ascii() {
+        let expected = format!("{} of `F`/`B` then {} of `L`/`R`", rows, cols);
+        return Err(ParseError::at(s, s, &expected));
+    }
+    let (row, col) = s.split_at(rows);
+    let row = binary(s, row, 'F', 'B')?;
+    let col = binary(s, col, 'L', 'R')?;
+    Ok(BoardingPass::new(row, col, plane))
 }
-fn input(fname: &str) -> io::Result<Vec<BoardingPass>> {
-    let file = File::open(fname)?;
-    let buf = io::BufReader::new(file);
-    Ok(buf
-        .lines()
-        .map(|line| parse_boarding_pass(&line.unwrap()))
-        .collect())
+
+fn binary(s: &str, part: &str, zero: char, one: char) -> Result<usize, ParseError> {
+    let mut num = 0;
+    for (idx, x) in part.char_indices() {
+        num = num * 2
+            + match x {
+                x if x == zero => 0,
+                x if x == one => 1,
+                _ => {
+                    let expected = format!("`{}` or `{}`", zero, one);
+                    return Err(ParseError::at(s, &part[idx..=idx], &expected));
+                }
+            };
+    }
+    Ok(num)
 }
 
 #[test]
 fn test_parsing() {
-    let tests = vec![
+    let tests = [
         (
             "BFFFBBFRRR",
             BoardingPass {
@@ -102,7 +140,36 @@ fn test_parsing() {
             },
         ),
     ];
+    let plane = Plane::default();
     for (input, pass) in tests.iter() {
-        assert_eq!(pass, &parse_boarding_pass(input));
+        assert_eq!(pass, &parse_boarding_pass(input, &plane).unwrap());
     }
+    let e = parse_boarding_pass("BFFFBBFRLX", &plane).unwrap_err();
+    assert_eq!((10, "X"), (e.column, e.found.as_str()));
+    assert!(parse_boarding_pass("BFFFBBF", &plane).is_err());
+
+    let small = Plane {
+        rows: 4,
+        columns: 2,
+    };
+    assert_eq!(
+        BoardingPass {
+            row: 2,
+            col: 1,
+            seat_id: 5,
+        },
+        parse_boarding_pass("BFR", &small).unwrap()
+    );
+    let e = parse_boarding_pass("BFFFBBFRRR", &small).unwrap_err();
+    assert_eq!("2 of `F`/`B` then 1 of `L`/`R`", e.expected);
+}
+
+#[test]
+fn test_empty() {
+    let passes = Matt::parse("").unwrap();
+    assert_eq!(Answer::Unsolved, Matt::part_one(&passes));
+    assert_eq!(Answer::Unsolved, Matt::part_two(&passes));
+    let passes = Matt::parse("BFFFBBFRRR\nBFFFBBFRRL\n").unwrap();
+    assert_eq!(Answer::Int(567), Matt::part_one(&passes));
+    assert_eq!(Answer::Unsolved, Matt::part_two(&passes));
 }
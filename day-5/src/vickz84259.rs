@@ -1,38 +1,87 @@
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::{self, BufRead};
 use std::str::FromStr;
 
+use aoc_common::params::{Parameter, Params};
+use aoc_common::{parse, Answer, ParseError, Result, Solver};
 use itertools::Itertools;
 
-struct BoardingPass {
+use crate::Plane;
+
+/// vickz84259's solution.
+pub struct Vickz84259;
+
+/// The IDs of the seats on the passes, with the plane they're on.
+pub struct Seats {
+    pub seat_ids: HashSet<u32>,
+    pub plane: Plane,
+}
+
+impl Solver for Vickz84259 {
+    type Input = Seats;
+
+    const PARAMETERS: &'static [Parameter] = crate::PARAMETERS;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Self::parse_with(input, &Params::defaults(Self::PARAMETERS))
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Input> {
+        let plane = Plane::from_params(params)?;
+        Ok(Seats {
+            seat_ids: get_seat_ids(input, &plane)?,
+            plane,
+        })
+    }
+
+    fn part_one(seats: &Self::Input) -> Answer {
+        part_1(&seats.seat_ids).map_or(Answer::Unsolved, Answer::from)
+    }
+
+    fn part_two(seats: &Self::Input) -> Answer {
+        part_2(&seats.seat_ids, &seats.plane).map_or(Answer::Unsolved, Answer::from)
+    }
+}
+
+/// The rows and columns a boarding pass could still be for, narrowed down
+/// one character at a time.
+pub struct BoardingPass {
     row_range: (u32, u32),
     col_range: (u32, u32),
+    /// How many seats there are in a row.
+    columns: u32,
 }
 
 #[derive(Debug)]
-struct PassParseError;
+struct PassParseError(&'static str);
 
 impl Default for BoardingPass {
     fn default() -> Self {
-        BoardingPass {
-            row_range: (0, 127),
-            col_range: (0, 7),
-        }
+        BoardingPass::on(&Plane::default())
     }
 }
 
 impl BoardingPass {
-    fn new() -> Self {
+    pub fn new() -> Self {
         Default::default()
     }
 
-    fn get_seat_id(row: u32, column: u32) -> u32 {
-        (row * 8) + column
+    /// A pass that could be for any seat on `plane`.
+    pub fn on(plane: &Plane) -> Self {
+        BoardingPass {
+            row_range: (0, plane.rows as u32 - 1),
+            col_range: (0, plane.columns as u32 - 1),
+            columns: plane.columns as u32,
+        }
+    }
+
+    /// The seat ID of the seat at `row` and `column`, in a plane `columns`
+    /// seats wide.
+    pub fn get_seat_id(row: u32, column: u32, columns: u32) -> u32 {
+        (row * columns) + column
     }
 
     fn set_range(range: &mut (u32, u32), lower: bool) {
-        let new_high = (range.1 + range.0 + 1) / 2;
+        let new_high = (range.1 + range.0).div_ceil(2);
         *range = if lower {
             (range.0, new_high - 1)
         } else {
@@ -41,68 +90,133 @@ impl BoardingPass {
     }
 
     fn partition(&mut self, character: &char) -> Result<(), PassParseError> {
-        match character {
-            'F' => BoardingPass::set_range(&mut self.row_range, true),
-            'B' => BoardingPass::set_range(&mut self.row_range, false),
-            'L' => BoardingPass::set_range(&mut self.col_range, true),
-            'R' => BoardingPass::set_range(&mut self.col_range, false),
-            _ => return Err(PassParseError),
+        let (range, lower) = match character {
+            'F' => (&mut self.row_range, true),
+            'B' => (&mut self.row_range, false),
+            'L' => (&mut self.col_range, true),
+            'R' => (&mut self.col_range, false),
+            _ => return Err(PassParseError("one of `F`, `B`, `L` or `R`")),
         };
+        if range.0 == range.1 {
+            return Err(PassParseError("no more partitions of a found seat"));
+        }
+        BoardingPass::set_range(range, lower);
         Ok(())
     }
 
-    fn seat_id(&self) -> u32 {
-        BoardingPass::get_seat_id(self.row_range.0, self.col_range.0)
+    /// The seat ID of the lowest row and column the pass could be for.
+    pub fn seat_id(&self) -> u32 {
+        BoardingPass::get_seat_id(self.row_range.0, self.col_range.0, self.columns)
     }
-}
 
-impl FromStr for BoardingPass {
-    type Err = PassParseError;
-
-    fn from_str(s: &str) -> Result<BoardingPass, PassParseError> {
-        let mut pass = BoardingPass::new();
+    /// Parses a pass for a seat on `plane`.
+    pub fn parse_on(s: &str, plane: &Plane) -> Result<BoardingPass, ParseError> {
+        let mut pass = BoardingPass::on(plane);
 
-        for character in s.chars() {
-            pass.partition(&character)?;
+        for (index, character) in s.char_indices() {
+            pass.partition(&character).map_err(|e| {
+                let span = &s[index..index + character.len_utf8()];
+                ParseError::at(s, span, e.0)
+            })?;
         }
 
         if pass.row_range.0 != pass.row_range.1 || pass.col_range.0 != pass.col_range.1 {
-            return Err(PassParseError);
+            return Err(ParseError::missing(s, "more of `F`, `B`, `L` or `R`"));
         }
         Ok(pass)
     }
 }
 
-fn get_passes() -> Vec<BoardingPass> {
-    let file = File::open("vickz84259.txt").expect("Unable to read file");
-    let lines = io::BufReader::new(file).lines().filter_map(Result::ok);
+impl FromStr for BoardingPass {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<BoardingPass, ParseError> {
+        BoardingPass::parse_on(s, &Plane::default())
+    }
+}
+
+/// Parses the boarding passes for seats on `plane`, one per line.
+pub fn get_passes(input: &str, plane: &Plane) -> Result<Vec<BoardingPass>, ParseError> {
+    parse::lines(input, |line| BoardingPass::parse_on(line, plane))
+}
 
-    lines.map(|string| string.parse().unwrap()).collect()
+/// Parses the boarding passes into the IDs of their seats.
+pub fn get_seat_ids(input: &str, plane: &Plane) -> Result<HashSet<u32>, ParseError> {
+    Ok(get_passes(input, plane)?
+        .iter()
+        .map(|pass| pass.seat_id())
+        .collect())
 }
 
-fn part_1(seat_ids: &HashSet<u32>) {
-    let highest_seat_id = seat_ids.iter().max().unwrap_or(&0u32);
-    println!("Highest Seat Id: {}", highest_seat_id);
+/// The highest seat ID, if there are any.
+pub fn part_1(seat_ids: &HashSet<u32>) -> Option<u32> {
+    seat_ids.iter().max().copied()
 }
 
-fn part_2(seat_ids: &HashSet<u32>) {
-    let seat_id = (9u32..119u32)
-        .cartesian_product(0u32..7u32)
-        .map(|product| BoardingPass::get_seat_id(product.0, product.1))
-        .filter(|seat_id| !seat_ids.contains(&seat_id))
+/// The ID of the only seat on `plane` with no pass, but with passes for the
+/// seats either side of it, if exactly one is like that.
+pub fn part_2(seat_ids: &HashSet<u32>, plane: &Plane) -> Option<u32> {
+    let (rows, columns) = (plane.rows as u32, plane.columns as u32);
+    (0..rows)
+        .cartesian_product(0..columns)
+        .map(|product| BoardingPass::get_seat_id(product.0, product.1, columns))
+        .filter(|seat_id| !seat_ids.contains(seat_id))
+        .filter(|&seat_id| {
+            seat_id > 0 && seat_ids.contains(&(seat_id - 1)) && seat_ids.contains(&(seat_id + 1))
+        })
         .exactly_one()
-        .unwrap();
-
-    println!("Seat id: {}", seat_id);
+        .ok()
 }
 
-fn main() {
-    let seat_ids: HashSet<u32> = get_passes().iter().map(|pass| pass.seat_id()).collect();
+/// Prints the answers for `input`.
+pub fn main(input: &str) -> Result<()> {
+    let Seats { seat_ids, plane } = Vickz84259::parse(input)?;
 
     println!("Part 1: \n----------");
-    part_1(&seat_ids);
+    match part_1(&seat_ids) {
+        Some(seat_id) => println!("Highest Seat Id: {}", seat_id),
+        None => println!("No boarding passes"),
+    }
 
     println!("----------");
     println!("Part 2: \n----------");
-    part_2(&seat_ids);
+    match part_2(&seat_ids, &plane) {
+        Some(seat_id) => println!("Seat id: {}", seat_id),
+        None => println!("No single empty seat between two taken ones"),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_plane() {
+    let seats = Vickz84259::parse("BFFFBBFRRR\nBFFFBBFRLR\n").unwrap();
+    assert_eq!(Answer::Int(567), Vickz84259::part_one(&seats));
+    assert_eq!(Answer::Int(566), Vickz84259::part_two(&seats));
+
+    // 8 rows of 2 seats, with every seat but 7 taken from 2 to 12.
+    let params = Params::from_args(&["rows=8", "columns=2"]).unwrap();
+    let params = params.resolve(Vickz84259::PARAMETERS).unwrap();
+    let passes: String = (2..=12)
+        .filter(|&seat| seat != 7)
+        .map(|seat: u32| {
+            let row: String = (0..3)
+                .rev()
+                .map(|bit| if (seat / 2) >> bit & 1 == 0 { 'F' } else { 'B' })
+                .collect();
+            format!(
+                "{}{}\n",
+                row,
+                if seat.is_multiple_of(2) { 'L' } else { 'R' }
+            )
+        })
+        .collect();
+    let seats = Vickz84259::parse_with(&passes, &params).unwrap();
+    assert_eq!(Answer::Int(12), Vickz84259::part_one(&seats));
+    assert_eq!(Answer::Int(7), Vickz84259::part_two(&seats));
+
+    // No seat is missing between two taken ones.
+    let seats = Vickz84259::parse_with("FFFL\nFFFR\n", &params).unwrap();
+    assert_eq!(Answer::Unsolved, Vickz84259::part_two(&seats));
+    let seats = Vickz84259::parse("").unwrap();
+    assert_eq!(Answer::Unsolved, Vickz84259::part_one(&seats));
 }
@@ -0,0 +1,68 @@
+// Random customs declaration forms.
+//
+// Each group starts from a few questions everyone answered "yes" to, and each
+// person adds some of their own.
+use std::collections::BTreeSet;
+
+use aoc_common::generate::{Generated, Rng};
+
+const QUESTIONS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+/// The answers of `size` groups of one to five people.
+pub fn input(rng: &mut Rng, size: usize) -> Generated {
+    let mut groups = vec![];
+    let (mut anyone, mut everyone) = (0, 0);
+    for _ in 0..size {
+        let shared = questions(rng, 0.2);
+        let people: Vec<BTreeSet<u8>> = (0..rng.range(1..=5))
+            .map(|_| {
+                let mut answers = questions(rng, 0.15);
+                answers.extend(&shared);
+                if answers.is_empty() {
+                    answers.insert(*rng.choose(QUESTIONS));
+                }
+                answers
+            })
+            .collect();
+
+        anyone += people.iter().flatten().collect::<BTreeSet<_>>().len();
+        everyone += QUESTIONS
+            .iter()
+            .filter(|question| people.iter().all(|answers| answers.contains(question)))
+            .count();
+
+        let mut group = String::new();
+        for answers in people {
+            let mut line: Vec<u8> = answers.into_iter().collect();
+            rng.shuffle(&mut line);
+            group.push_str(&String::from_utf8_lossy(&line));
+            group.push('\n');
+        }
+        groups.push(group);
+    }
+    Generated::new(groups.join("\n"), anyone, everyone)
+}
+
+/// Each question, with probability `p`.
+fn questions(rng: &mut Rng, p: f64) -> BTreeSet<u8> {
+    QUESTIONS
+        .iter()
+        .copied()
+        .filter(|_| rng.chance(p))
+        .collect()
+}
+
+#[test]
+fn test_generate() {
+    use aoc_common::Solver;
+
+    use crate::matt::Matt;
+
+    for seed in 0..20 {
+        let generated = input(&mut Rng::new(seed), 100);
+        let groups = Matt::parse(&generated.input).unwrap();
+        assert_eq!(100, groups.len());
+        assert_eq!(generated.one, Matt::part_one(&groups));
+        assert_eq!(generated.two, Matt::part_two(&groups));
+    }
+}
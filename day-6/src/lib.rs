@@ -0,0 +1,16 @@
+// --- Day 6: Custom Customs ---
+//
+// https://adventofcode.com/2020/day/6
+use aoc_common::Solution;
+
+pub mod generate;
+pub mod matt;
+pub mod vickz84259;
+
+/// Every author's solution for the day.
+pub fn solutions() -> Vec<Solution> {
+    vec![
+        Solution::new::<matt::Matt>(6, "matt"),
+        Solution::new::<vickz84259::Vickz84259>(6, "vickz84259"),
+    ]
+}
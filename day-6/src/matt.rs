@@ -2,17 +2,51 @@
 //
 // https://adventofcode.com/2020/day/6
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::{self, BufRead};
 
-type Answers = HashSet<char>;
-type GroupAnswers = Vec<Answers>;
-enum AnswersType {
+use aoc_common::records::records;
+use aoc_common::{Answer, ParseError, Result, Solver};
+
+/// Matt's solution.
+pub struct Matt;
+
+impl Solver for Matt {
+    type Input = Vec<GroupAnswers>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut groups = vec![];
+        for record in records(input.as_bytes()) {
+            // Leading whitespace isn't an answer.
+            let group =
+                record?.parse(|text| vec_to_group(text, text.lines().map(str::trim).collect()))?;
+            groups.push(group);
+        }
+        Ok(groups)
+    }
+
+    fn part_one(groups: &Self::Input) -> Answer {
+        part_one(groups).into()
+    }
+
+    fn part_two(groups: &Self::Input) -> Answer {
+        part_two(groups).into()
+    }
+}
+
+/// The questions one person answered "yes" to.
+pub type Answers = HashSet<char>;
+/// Everyone in a group's answers.
+pub type GroupAnswers = Vec<Answers>;
+
+/// Which of a group's questions to count.
+pub enum AnswersType {
+    /// Those everyone answered.
     Intersection,
+    /// Those anyone answered.
     Union,
 }
 
-fn count_common_answers(mut group: GroupAnswers, criteria: AnswersType) -> usize {
+/// How many questions the group answered, by `criteria`.
+pub fn count_common_answers(mut group: GroupAnswers, criteria: AnswersType) -> usize {
     match criteria {
         AnswersType::Union => group
             .into_iter()
@@ -27,48 +61,59 @@ fn count_common_answers(mut group: GroupAnswers, criteria: AnswersType) -> usize
         }
     }
 }
-fn part_one(v: &Vec<GroupAnswers>) -> usize {
-    v.into_iter()
+
+/// The sum over the groups of how many questions anyone answered.
+pub fn part_one(v: &[GroupAnswers]) -> usize {
+    v.iter()
         .map(|g| count_common_answers(g.to_vec(), AnswersType::Union))
         .sum()
 }
-fn part_two(v: &Vec<GroupAnswers>) -> usize {
-    v.into_iter()
+
+/// The sum over the groups of how many questions everyone answered.
+pub fn part_two(v: &[GroupAnswers]) -> usize {
+    v.iter()
         .map(|g| count_common_answers(g.to_vec(), AnswersType::Intersection))
         .sum()
 }
-fn main() {
-    let groups = load_input("src/matt.txt").unwrap();
+
+/// Prints the answers for `input`.
+pub fn main(input: &str) -> Result<()> {
+    let groups = Matt::parse(input)?;
 
     println!("Part One: {} ", part_one(&groups));
     println!("Part Two: {} ", part_two(&groups));
+    Ok(())
 }
 
-fn vec_to_group(v: Vec<String>) -> GroupAnswers {
+/// Collects a group's answers from its lines, which must be slices of `input`.
+pub fn vec_to_group(input: &str, v: Vec<&str>) -> Result<GroupAnswers, ParseError> {
     v.into_iter()
-        .map(|line| line.chars().collect::<Answers>())
+        .map(
+            |line| match line.char_indices().find(|x| !x.1.is_ascii_lowercase()) {
+                Some((idx, c)) => {
+                    let span = &line[idx..idx + c.len_utf8()];
+                    Err(ParseError::at(input, span, "a question from `a` to `z`"))
+                }
+                None => Ok(line.chars().collect::<Answers>()),
+            },
+        )
         .collect()
 }
-fn load_input(fname: &str) -> io::Result<Vec<GroupAnswers>> {
-    let file = File::open(fname)?;
-    let buf = io::BufReader::new(file);
-    let mut groups = vec![];
-    let mut group = vec![];
-    for line in buf.lines() {
-        let line = line?;
-        if line.is_empty() {
-            groups.push(vec_to_group(group.clone()));
-            group.clear();
-        } else {
-            group.push(line)
-        }
+
+#[test]
+fn test_parse_error() {
+    let e = Matt::parse("abc\n\na\nbC\n").err().unwrap();
+    match e {
+        aoc_common::Error::Parse(e) => assert_eq!((4, 2, "C"), (e.line, e.column, &*e.found)),
+        e => panic!("unexpected error {}", e),
     }
-    groups.push(vec_to_group(group.clone()));
-    Ok(groups)
 }
 
 #[test]
-fn test_parsing() {
-    let groups = load_input("src/matt_test.txt").unwrap();
-    assert_eq!(11, part_one(&groups))
+fn test_blank_lines() {
+    // Blank lines at either end used to make an empty group, which has no
+    // first person to intersect the others' answers with.
+    let groups = Matt::parse("\r\nabc\r\n\r\n\r\na\r\n b\r\n\r\n\r\n").unwrap();
+    assert_eq!(Answer::Int(5), Matt::part_one(&groups));
+    assert_eq!(Answer::Int(3), Matt::part_two(&groups));
 }
@@ -1,15 +1,38 @@
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{self, BufRead};
 use std::mem::size_of;
 use std::str::FromStr;
 
-type Groups = Vec<Group>;
+use aoc_common::records::records;
+use aoc_common::{Answer, ParseError, Result, Solver};
 
+/// vickz84259's solution.
+pub struct Vickz84259;
+
+impl Solver for Vickz84259 {
+    type Input = Groups;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        get_groups(input)
+    }
+
+    fn part_one(groups: &Self::Input) -> Answer {
+        part_1(groups).into()
+    }
+
+    fn part_two(groups: &Self::Input) -> Answer {
+        part_2(groups).into()
+    }
+}
+
+pub type Groups = Vec<Group>;
+
+/// A group's answers.
 #[derive(Debug)]
-struct Group {
-    number: u32,
-    questions: HashMap<char, u32>,
+pub struct Group {
+    /// How many people are in the group.
+    pub number: u32,
+    /// How many of them answered each question.
+    pub questions: HashMap<char, u32>,
 }
 
 impl Group {
@@ -22,54 +45,44 @@ impl Group {
 }
 
 impl FromStr for Group {
-    type Err = &'static str;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Group, Self::Err> {
         let mut group = Group::new();
 
-        s.split_whitespace().for_each(|line| {
+        for line in s.split_whitespace() {
             group.number += 1;
-            line.chars().for_each(|c| {
+            for (index, c) in line.char_indices() {
+                if !c.is_ascii_lowercase() {
+                    let span = &line[index..index + c.len_utf8()];
+                    return Err(ParseError::at(s, span, "a question from `a` to `z`"));
+                }
                 let value = group.questions.entry(c).or_insert(0);
                 *value += 1;
-            });
-        });
+            }
+        }
         Ok(group)
     }
 }
 
-fn get_groups() -> Groups {
-    let file = File::open("input.txt").expect("Unable to open file");
-    let mut reader = io::BufReader::new(file);
-
+/// Parses the groups, which are separated by blank lines.
+pub fn get_groups(input: &str) -> Result<Groups> {
     let mut groups: Groups = Vec::with_capacity(25 * size_of::<Group>());
-    let mut reader_buffer = String::with_capacity(30);
-    let mut group_buffer = String::with_capacity(30 * 5);
 
-    loop {
-        let no_of_bytes = reader.read_line(&mut reader_buffer).unwrap_or(0);
-
-        group_buffer.push_str(&reader_buffer);
-        if &reader_buffer == "\n" || &reader_buffer == "" {
-            groups.push(group_buffer.parse().unwrap());
-            group_buffer.clear();
-        }
-
-        reader_buffer.clear();
-        if no_of_bytes == 0 {
-            break;
-        }
+    for record in records(input.as_bytes()) {
+        groups.push(record?.parse(str::parse)?);
     }
-    groups
+    Ok(groups)
 }
 
-fn part_1(groups: &Groups) {
-    let sum: usize = groups.iter().map(|group| group.questions.len()).sum();
-    println!("Answer: {}", sum);
+/// The sum over the groups of how many questions anyone answered.
+pub fn part_1(groups: &[Group]) -> usize {
+    groups.iter().map(|group| group.questions.len()).sum()
 }
 
-fn part_2(groups: &Groups) {
-    let sum: usize = groups
+/// The sum over the groups of how many questions everyone answered.
+pub fn part_2(groups: &[Group]) -> usize {
+    groups
         .iter()
         .map(|group| {
             group
@@ -78,17 +91,18 @@ fn part_2(groups: &Groups) {
                 .filter(|entry| *entry.1 == group.number)
                 .count()
         })
-        .sum();
-    println!("Answer: {}", sum);
+        .sum()
 }
 
-fn main() {
-    let groups = get_groups();
+/// Prints the answers for `input`.
+pub fn main(input: &str) -> Result<()> {
+    let groups = get_groups(input)?;
 
     println!("Part 1: \n----------");
-    part_1(&groups);
+    println!("Answer: {}", part_1(&groups));
 
     println!("----------");
     println!("Part 2: \n----------");
-    part_2(&groups);
+    println!("Answer: {}", part_2(&groups));
+    Ok(())
 }
@@ -0,0 +1,128 @@
+// Random bag rule sets, without any bag that ends up inside itself.
+//
+// Bags are put in layers, and a bag only ever holds bags from deeper layers,
+// so the rules can't go round in circles. Shiny gold sits in the middle layer,
+// so that there are bags both around and inside it.
+use aoc_common::generate::{Generated, Rng};
+
+const ADJECTIVES: [&str; 20] = [
+    "bright", "clear", "dark", "dim", "dotted", "drab", "dull", "faded", "light", "mirrored",
+    "muted", "pale", "plaid", "posh", "shiny", "striped", "vibrant", "wavy", "dusky", "glossy",
+];
+const COLORS: [&str; 20] = [
+    "aqua",
+    "beige",
+    "black",
+    "blue",
+    "bronze",
+    "brown",
+    "chartreuse",
+    "coral",
+    "crimson",
+    "cyan",
+    "gold",
+    "gray",
+    "green",
+    "indigo",
+    "lavender",
+    "lime",
+    "magenta",
+    "maroon",
+    "olive",
+    "plum",
+];
+const LAYERS: usize = 7;
+
+/// `size` rules, one for each bag, including shiny gold.
+pub fn input(rng: &mut Rng, size: usize) -> Generated {
+    let mut bags: Vec<String> = ADJECTIVES
+        .iter()
+        .flat_map(|adjective| {
+            COLORS
+                .iter()
+                .map(move |color| format!("{} {}", adjective, color))
+        })
+        .filter(|bag| bag != "shiny gold")
+        .collect();
+    rng.shuffle(&mut bags);
+    bags.truncate(size.clamp(1, bags.len() + 1) - 1);
+    let gold = bags.len() / 2;
+    bags.insert(gold, "shiny gold".to_string());
+
+    // Bag `i` is in layer `layer(i)`, and only holds bags that come after it.
+    let count = bags.len();
+    let layer = |i: usize| i * LAYERS / count;
+    let rules: Vec<Vec<(u64, usize)>> = (0..count)
+        .map(|i| {
+            let deeper: Vec<_> = (i + 1..count).filter(|&j| layer(j) > layer(i)).collect();
+            let mut held = vec![];
+            for _ in 0..rng.range(0..=4) {
+                if deeper.is_empty() {
+                    break;
+                }
+                let bag = *rng.choose(&deeper);
+                if held.iter().all(|&(_, other)| other != bag) {
+                    held.push((rng.range(1..=5), bag));
+                }
+            }
+            held
+        })
+        .collect();
+
+    // Work back from the deepest bags, which hold nothing.
+    let mut holds_gold = vec![false; count];
+    let mut inside = vec![0; count];
+    for i in (0..count).rev() {
+        holds_gold[i] = rules[i]
+            .iter()
+            .any(|&(_, bag)| bag == gold || holds_gold[bag]);
+        inside[i] = rules[i]
+            .iter()
+            .map(|&(number, bag)| number * (1 + inside[bag]))
+            .sum::<u64>();
+    }
+
+    let mut lines: Vec<String> = bags
+        .iter()
+        .zip(&rules)
+        .map(|(bag, held)| format!("{} bags contain {}.\n", bag, contents(&bags, held)))
+        .collect();
+    rng.shuffle(&mut lines);
+    Generated::new(
+        lines.concat(),
+        holds_gold.iter().filter(|&&holds| holds).count(),
+        inside[gold],
+    )
+}
+
+fn contents(bags: &[String], held: &[(u64, usize)]) -> String {
+    if held.is_empty() {
+        return "no other bags".to_string();
+    }
+    held.iter()
+        .map(|&(number, bag)| {
+            let plural = if number == 1 { "" } else { "s" };
+            format!("{} {} bag{}", number, bags[bag], plural)
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[test]
+fn test_generate() {
+    use aoc_common::Solver;
+
+    use crate::matt::Matt;
+
+    for seed in 0..20 {
+        let generated = input(&mut Rng::new(seed), 60);
+        let bags = Matt::parse(&generated.input).unwrap();
+        assert_eq!(60, bags.ruleset.len());
+        assert_eq!(generated.one, Matt::part_one(&bags));
+        assert_eq!(generated.two, Matt::part_two(&bags));
+    }
+    assert_eq!(
+        "shiny gold bags contain no other bags.\n",
+        input(&mut Rng::new(0), 1).input
+    );
+}
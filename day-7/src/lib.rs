@@ -0,0 +1,20 @@
+// --- Day 7: Handy Haversacks ---
+//
+// https://adventofcode.com/2020/day/7
+use aoc_common::params::Parameter;
+use aoc_common::Solution;
+
+pub mod generate;
+pub mod matt;
+
+/// The puzzle's constants, which every author's solution takes.
+pub const PARAMETERS: &[Parameter] = &[Parameter {
+    name: "bag",
+    default: "shiny gold",
+    about: "the colour of your bag",
+}];
+
+/// Every author's solution for the day.
+pub fn solutions() -> Vec<Solution> {
+    vec![Solution::new::<matt::Matt>(7, "matt")]
+}
@@ -1,34 +1,82 @@
 // --- Day 7: Handy Haversacks ---
 //
 // https://adventofcode.com/2020/day/7
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::{self, BufRead};
+use std::collections::{HashMap, HashSet};
 
-type Color = String;
-type Rule = (usize, Color);
-type Rules = Vec<Rule>;
-type RuleSet = HashMap<Color, Rules>;
+use aoc_common::params::{Parameter, Params};
+use aoc_common::{parse, Answer, ParseError, Result, Solver};
 
-fn part_one(rs: &RuleSet, c: &str) -> usize {
+/// Matt's solution.
+pub struct Matt;
+
+/// The rules, with the colour of your bag.
+pub struct Bags {
+    pub ruleset: RuleSet,
+    pub bag: Color,
+}
+
+impl Solver for Matt {
+    type Input = Bags;
+
+    const PARAMETERS: &'static [Parameter] = crate::PARAMETERS;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Self::parse_with(input, &Params::defaults(Self::PARAMETERS))
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Input> {
+        let ruleset: RuleSet = parse::lines(input, parse_rule_line)?;
+        check_rules(input, &ruleset)?;
+        // Part two needs a rule for the bag to count what it holds.
+        let bag: Color = params.get("bag", "a colour")?;
+        if !ruleset.contains_key(&bag) {
+            let expected = format!("a rule for `{}` bags", bag);
+            return Err(ParseError::missing(input, &expected).into());
+        }
+        Ok(Bags { ruleset, bag })
+    }
+
+    fn part_one(bags: &Self::Input) -> Answer {
+        part_one(&bags.ruleset, &bags.bag).into()
+    }
+
+    fn part_two(bags: &Self::Input) -> Answer {
+        part_two(&bags.ruleset, &bags.bag).into()
+    }
+}
+
+/// A bag's colour, e.g. `shiny gold`.
+pub type Color = String;
+/// How many bags of a colour a bag must hold.
+pub type Rule = (usize, Color);
+pub type Rules = Vec<Rule>;
+/// What each colour of bag must hold.
+pub type RuleSet = HashMap<Color, Rules>;
+
+/// How many colours of bag end up holding a bag of colour `c`. A colour
+/// with no rule holds nothing; the rules mustn't go round in a circle.
+pub fn part_one(rs: &RuleSet, c: &str) -> usize {
     let mut count = 0;
-    for color in rs.keys() {
-        count += recursive_find(rs, c, rs.get(color).unwrap());
+    for rules in rs.values() {
+        count += recursive_find(rs, c, rules);
     }
     count
 }
 
 fn recursive_find(rs: &RuleSet, c: &str, rules: &[Rule]) -> usize {
     for rule in rules {
-        if rule.1 == c || recursive_find(rs, c, rs.get(&rule.1).unwrap()) == 1 {
+        let held = rs.get(&rule.1).map_or(&[][..], |rules| rules);
+        if rule.1 == c || recursive_find(rs, c, held) == 1 {
             return 1;
         }
     }
     0
 }
 
-fn part_two(rs: &RuleSet, c: &str) -> usize {
-    let rules = rs.get(c).unwrap();
+/// How many bags a bag of colour `c` ends up holding. A colour with no rule
+/// holds nothing; the rules mustn't go round in a circle.
+pub fn part_two(rs: &RuleSet, c: &str) -> usize {
+    let rules = rs.get(c).map_or(&[][..], |rules| rules);
     if rules.is_empty() {
         0
     } else {
@@ -40,48 +88,152 @@ fn part_two(rs: &RuleSet, c: &str) -> usize {
     }
 }
 
-fn main() {
-    let ruleset = load_input("input.txt").unwrap();
+/// Prints the answers for `input`.
+pub fn main(input: &str) -> Result<()> {
+    let bags = Matt::parse(input)?;
 
-    println!("Part One: {} ", part_one(&ruleset, "shiny gold"));
-    println!("Part Two: {} ", part_two(&ruleset, "shiny gold"));
+    println!("Part One: {} ", Matt::part_one(&bags));
+    println!("Part Two: {} ", Matt::part_two(&bags));
+    Ok(())
 }
 
-fn parse_rule_line(l: String) -> (Color, Rules) {
-    let mut rules: Rules = vec![];
-    let parts: Vec<_> = l.split("bags contain").collect();
-    let color: Color = parts[0].trim().to_string();
-    for part in parts[1].split(',') {
+/// Parses a rule like `bright white bags contain 1 shiny gold bag.`
+pub fn parse_rule_line(l: &str) -> Result<(Color, Rules), ParseError> {
+    let (color, rules) = rule_spans(l)?;
+    let rules = rules
+        .into_iter()
+        .map(|(num, sub_color)| (num, sub_color.to_string()))
+        .collect();
+    Ok((color.to_string(), rules))
+}
+
+/// A rule's colour and what it holds, as slices of the line it's on.
+type RuleSpans<'a> = (&'a str, Vec<(usize, &'a str)>);
+
+fn rule_spans(l: &str) -> Result<RuleSpans<'_>, ParseError> {
+    let mut rules = vec![];
+    let (color, contents) =
+        parse::split_once(l, l, "bags contain", "`<color> bags contain <bags>`")?;
+    for part in contents.split(',') {
         if part.contains("no other bags") {
             continue;
         }
-        let mut subs = part.trim().split(' ');
-        let num: usize = subs.next().unwrap().trim().parse().unwrap();
-        let sub_color: String = subs.take(2).fold(String::new(), |acc, x| {
-            format!("{} {}", acc, x).trim().to_string()
-        });
-        rules.push((num, sub_color));
+        let (num, subs) = parse::split_once(l, part.trim(), " ", "`<count> <color> bags`")?;
+        let num: usize = parse::value(l, num, "a number of bags")?;
+        // The colour is the first two words, before `bag` or `bags`.
+        let end = subs
+            .match_indices(' ')
+            .nth(1)
+            .map_or(subs.len(), |(i, _)| i);
+        rules.push((num, &subs[..end]));
     }
-    (color, rules)
+    Ok((color.trim(), rules))
 }
-fn load_input(fname: &str) -> io::Result<RuleSet> {
-    let file = File::open(fname)?;
-    let buf = io::BufReader::new(file);
-    let mut ruleset: RuleSet = HashMap::new();
-    for line in buf.lines() {
-        let (color, rules) = parse_rule_line(line?);
-        ruleset.insert(color, rules);
+
+/// Checks that every colour a rule holds has a rule of its own, and that no
+/// bag ends up holding a bag of its own colour, which would never end.
+fn check_rules(input: &str, rs: &RuleSet) -> Result<(), ParseError> {
+    let mut colors: Vec<_> = rs.keys().collect();
+    colors.sort();
+    let mut searched = HashMap::new();
+    let mut cycles = HashSet::new();
+    for color in colors {
+        find_cycles(rs, color, &mut searched, &mut cycles);
     }
-    Ok(ruleset)
+
+    parse::lines(input, |l| {
+        let (color, rules) = rule_spans(l)?;
+        for (_, sub_color) in rules {
+            if !rs.contains_key(sub_color) {
+                return Err(ParseError::at(l, sub_color, "a colour with a rule"));
+            }
+            if cycles.contains(&(color, sub_color)) {
+                let expected = format!("a colour that doesn't end up holding `{}` bags", color);
+                return Err(ParseError::at(l, sub_color, &expected));
+            }
+        }
+        Ok(())
+    })
 }
 
+/// Searches the bags `color` holds, noting every rule that holds a bag the
+/// search is still inside of, and so closes a cycle. `searched` has each
+/// colour searched from, and whether the search is still inside it.
+fn find_cycles<'a>(
+    rs: &'a RuleSet,
+    color: &'a str,
+    searched: &mut HashMap<&'a str, bool>,
+    cycles: &mut HashSet<(&'a str, &'a str)>,
+) {
+    if searched.contains_key(color) {
+        return;
+    }
+    searched.insert(color, true);
+    for (_, sub_color) in rs.get(color).into_iter().flatten() {
+        match searched.get(sub_color.as_str()) {
+            Some(true) => {
+                cycles.insert((color, sub_color));
+            }
+            Some(false) => {}
+            None => find_cycles(rs, sub_color, searched, cycles),
+        }
+    }
+    searched.insert(color, false);
+}
 #[test]
-fn test_one() {
-    let ruleset = load_input("test.txt").unwrap();
-    assert_eq!(4, part_one(&ruleset, "shiny gold"));
+fn test_parse_error() {
+    let e = parse_rule_line("faded blue bags contain two dotted black bags.").unwrap_err();
+    assert_eq!((25, "two"), (e.column, e.found.as_str()));
+    let e = parse_rule_line("faded blue bags").unwrap_err();
+    assert_eq!((1, "faded blue bags"), (e.column, e.found.as_str()));
 }
+
 #[test]
-fn test_two() {
-    let ruleset = load_input("test2.txt").unwrap();
-    assert_eq!(126, part_two(&ruleset, "shiny gold"));
+fn test_bag() {
+    let rules = "faded blue bags contain 2 dotted black bags.\n\
+                 dotted black bags contain no other bags.\n";
+    let params = |bag: &str| {
+        Params::from_args(&[format!("bag={}", bag)])
+            .unwrap()
+            .resolve(Matt::PARAMETERS)
+            .unwrap()
+    };
+    let bags = Matt::parse_with(rules, &params("dotted black")).unwrap();
+    assert_eq!(Answer::Int(1), Matt::part_one(&bags));
+    assert_eq!(Answer::Int(0), Matt::part_two(&bags));
+    let e = Matt::parse(rules).err().unwrap();
+    assert!(e
+        .to_string()
+        .contains("line 3, column 1: expected a rule for `shiny gold` bags"));
+    assert!(Matt::parse("").is_err());
+}
+
+#[test]
+fn test_check_rules() {
+    let parse_error = |input: &str| match Matt::parse_with(input, &Params::defaults(&[])) {
+        Err(aoc_common::Error::Parse(e)) => e,
+        _ => panic!("{} parsed", input),
+    };
+    let e = parse_error(
+        "shiny gold bags contain 2 dotted black bags.\n\
+         dotted black bags contain 1 faded blue bag.\n",
+    );
+    assert_eq!((2, 29, "faded blue"), (e.line, e.column, e.found.as_str()));
+    assert_eq!("a colour with a rule", e.expected);
+
+    let e = parse_error(
+        "shiny gold bags contain 2 dotted black bags.\n\
+         dotted black bags contain 1 faded blue bag.\n\
+         faded blue bags contain 3 shiny gold bags.\n",
+    );
+    assert_eq!(
+        (1, 27, "dotted black"),
+        (e.line, e.column, e.found.as_str())
+    );
+    assert_eq!(
+        "a colour that doesn't end up holding `shiny gold` bags",
+        e.expected
+    );
+    let e = parse_error("shiny gold bags contain 1 shiny gold bag.\n");
+    assert_eq!((1, 27), (e.line, e.column));
 }
@@ -0,0 +1,298 @@
+// A program's control flow, worked out without running it.
+//
+// Every instruction leads to exactly one place: the next instruction, or for
+// a `jmp` its target, which may be the end of the program or outside it
+// altogether. So the instructions reachable from the first are just the ones
+// the program runs, and every loop is a single cycle: the strongly connected
+// components of more than one instruction, or of a `jmp +0`. The accumulator
+// never decides where to go, so none of this depends on it.
+use std::fmt;
+
+use crate::machine::argument;
+use crate::matt::Instruction;
+use crate::repair;
+
+/// Where an instruction leads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Next {
+    Instruction(usize),
+    /// Just past the last instruction, where the program terminates.
+    End,
+    /// A `jmp` target outside the program.
+    OutOfRange(isize),
+}
+
+/// The control-flow graph of a program.
+#[derive(Debug, Clone)]
+pub struct Cfg<'a> {
+    program: &'a [Instruction],
+    next: Vec<Next>,
+}
+
+impl<'a> Cfg<'a> {
+    pub fn new(program: &'a [Instruction]) -> Cfg<'a> {
+        let len = program.len() as isize;
+        let next = program
+            .iter()
+            .enumerate()
+            .map(|(pc, &instruction)| {
+                let target = match instruction {
+                    Instruction::Jmp(sign, x) => pc as isize + argument(sign, x),
+                    _ => pc as isize + 1,
+                };
+                match target {
+                    t if t == len => Next::End,
+                    t if (0..len).contains(&t) => Next::Instruction(t as usize),
+                    t => Next::OutOfRange(t),
+                }
+            })
+            .collect();
+        Cfg { program, next }
+    }
+
+    /// Where the instruction at `pc` leads.
+    pub fn next(&self, pc: usize) -> Next {
+        self.next[pc]
+    }
+
+    /// Every loop, each in the order it runs from its lowest pc, ordered by
+    /// that pc.
+    pub fn loops(&self) -> Vec<Vec<usize>> {
+        // Follows the graph from each instruction not yet seen, numbering the
+        // instructions by which walk first saw them: a walk that comes back to
+        // one of its own has found a loop.
+        let mut walk = vec![None; self.next.len()];
+        let mut loops = vec![];
+        for start in 0..self.next.len() {
+            let mut pc = start;
+            while walk[pc].is_none() {
+                walk[pc] = Some(start);
+                match self.next[pc] {
+                    Next::Instruction(next) => pc = next,
+                    _ => break,
+                }
+            }
+            if walk[pc] == Some(start) && matches!(self.next[pc], Next::Instruction(_)) {
+                loops.push(self.cycle(pc));
+            }
+        }
+        loops.sort();
+        loops
+    }
+
+    /// The loop through `pc`, if there is one.
+    pub fn loop_through(&self, pc: usize) -> Option<Vec<usize>> {
+        self.loops().into_iter().find(|l| l.contains(&pc))
+    }
+
+    /// The cycle through `pc`, which must be on one, from its lowest pc.
+    fn cycle(&self, pc: usize) -> Vec<usize> {
+        let mut cycle = vec![pc];
+        while let Next::Instruction(next) = self.next[*cycle.last().unwrap()] {
+            if next == pc {
+                break;
+            }
+            cycle.push(next);
+        }
+        let lowest = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap();
+        cycle.rotate_left(lowest);
+        cycle
+    }
+
+    /// Whether each instruction can be reached from the first.
+    pub fn reachable(&self) -> Vec<bool> {
+        let mut reachable = vec![false; self.next.len()];
+        let mut next = match self.next.is_empty() {
+            true => Next::End,
+            false => Next::Instruction(0),
+        };
+        while let Next::Instruction(pc) = next {
+            if reachable[pc] {
+                break;
+            }
+            reachable[pc] = true;
+            next = self.next[pc];
+        }
+        reachable
+    }
+
+    /// The instructions that can't be reached from the first.
+    pub fn unreachable(&self) -> Vec<usize> {
+        let reachable = self.reachable();
+        (0..reachable.len()).filter(|&pc| !reachable[pc]).collect()
+    }
+
+    /// Whether following the program from each instruction leads to the end,
+    /// with one more entry for the end itself.
+    pub fn terminating(&self) -> Vec<bool> {
+        repair::terminating(self.program)
+    }
+
+    /// Every `jmp` whose target is outside the program, with the target.
+    pub fn out_of_range(&self) -> Vec<(usize, isize)> {
+        (0..self.next.len())
+            .filter_map(|pc| match self.next[pc] {
+                Next::OutOfRange(target) => Some((pc, target)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The graph in Graphviz's DOT language, with the instructions in
+    /// `highlight`, like a loop, and the edges between them in red.
+    /// Instructions that lead to the end are filled green, unreachable ones
+    /// are dashed, and out-of-range targets are drawn outside the program.
+    pub fn dot(&self, highlight: &[usize]) -> String {
+        let reachable = self.reachable();
+        let terminating = self.terminating();
+        let mut dot =
+            String::from("digraph program {\n    node [shape=box, fontname=monospace];\n");
+        for (pc, instruction) in self.program.iter().enumerate() {
+            let mut attributes = vec![format!("label=\"{}: {}\"", pc, instruction)];
+            if terminating[pc] {
+                attributes.push("style=filled, fillcolor=palegreen".to_string());
+            } else if !reachable[pc] {
+                attributes.push("style=dashed".to_string());
+            }
+            if highlight.contains(&pc) {
+                attributes.push("color=red, penwidth=2".to_string());
+            }
+            dot += &format!("    {} [{}];\n", pc, attributes.join(", "));
+        }
+        dot += "    end [shape=doublecircle];\n";
+
+        for (pc, &next) in self.next.iter().enumerate() {
+            match next {
+                Next::Instruction(next) if highlight.contains(&pc) && highlight.contains(&next) => {
+                    dot += &format!("    {} -> {} [color=red, penwidth=2];\n", pc, next)
+                }
+                Next::Instruction(next) => dot += &format!("    {} -> {};\n", pc, next),
+                Next::End => dot += &format!("    {} -> end;\n", pc),
+                Next::OutOfRange(target) => {
+                    dot += &format!(
+                        "    out{} [label=\"{}\", shape=plaintext, fontcolor=red];\n",
+                        pc, target
+                    );
+                    dot += &format!("    {} -> out{} [style=dashed, color=red];\n", pc, pc);
+                }
+            }
+        }
+        dot + "}\n"
+    }
+}
+
+/// A summary of the analysis.
+impl fmt::Display for Cfg<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let terminating = self.terminating();
+        let terminating: Vec<_> = (0..self.next.len()).filter(|&pc| terminating[pc]).collect();
+        writeln!(
+            f,
+            "{} instructions, {} reachable from the first",
+            self.next.len(),
+            self.next.len() - self.unreachable().len()
+        )?;
+        writeln!(f, "Unreachable: {}", ranges(&self.unreachable()))?;
+        writeln!(f, "Lead to the end: {}", ranges(&terminating))?;
+
+        let out_of_range: Vec<_> = self
+            .out_of_range()
+            .iter()
+            .map(|(pc, target)| format!("{} to {}", pc, target))
+            .collect();
+        match out_of_range.len() {
+            0 => writeln!(f, "Jumps out of the program: none")?,
+            _ => writeln!(f, "Jumps out of the program: {}", out_of_range.join(", "))?,
+        }
+
+        let loops = self.loops();
+        writeln!(f, "Loops: {}", loops.len())?;
+        for l in loops {
+            let pcs: Vec<_> = l.iter().map(usize::to_string).collect();
+            writeln!(f, "    {} instructions: {}", l.len(), pcs.join(" -> "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Sorted pcs, with runs written as e.g. `5-8`.
+fn ranges(pcs: &[usize]) -> String {
+    let mut runs: Vec<(usize, usize)> = vec![];
+    for &pc in pcs {
+        match runs.last_mut() {
+            Some((_, end)) if *end + 1 == pc => *end = pc,
+            _ => runs.push((pc, pc)),
+        }
+    }
+    if runs.is_empty() {
+        return "none".to_string();
+    }
+    let runs: Vec<_> = runs
+        .into_iter()
+        .map(|(start, end)| match start == end {
+            true => start.to_string(),
+            false => format!("{}-{}", start, end),
+        })
+        .collect();
+    runs.join(", ")
+}
+
+#[test]
+fn test_cfg() {
+    use aoc_common::parse;
+
+    let program =
+        |input: &str| -> Vec<Instruction> { parse::lines(input, crate::matt::parse_line).unwrap() };
+
+    let example =
+        program("nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n");
+    let cfg = Cfg::new(&example);
+    assert_eq!(Next::Instruction(6), cfg.next(2));
+    assert_eq!(Next::End, cfg.next(8));
+    assert_eq!(vec![vec![1, 2, 6, 7, 3, 4]], cfg.loops());
+    assert_eq!(Some(vec![1, 2, 6, 7, 3, 4]), cfg.loop_through(7));
+    assert_eq!(None, cfg.loop_through(5));
+    assert_eq!(vec![5, 8], cfg.unreachable());
+    assert!(cfg.out_of_range().is_empty());
+    assert_eq!(
+        "9 instructions, 7 reachable from the first\n\
+         Unreachable: 5, 8\n\
+         Lead to the end: 8\n\
+         Jumps out of the program: none\n\
+         Loops: 1\n    6 instructions: 1 -> 2 -> 6 -> 7 -> 3 -> 4\n",
+        cfg.to_string()
+    );
+
+    let escaping = program("jmp +6\nacc +1\njmp -3\njmp +0\nnop +0\n");
+    let cfg = Cfg::new(&escaping);
+    assert_eq!(vec![(0, 6), (2, -1)], cfg.out_of_range());
+    assert_eq!(vec![vec![3]], cfg.loops());
+    assert_eq!(vec![1, 2, 3, 4], cfg.unreachable());
+    assert_eq!(
+        vec![false, false, false, false, true, true],
+        cfg.terminating()
+    );
+    assert_eq!("1-4", ranges(&cfg.unreachable()));
+
+    let looping = program("nop +0\njmp -1\nacc +1\njmp +5\nacc +1\n");
+    let cfg = Cfg::new(&looping);
+    assert_eq!(
+        "digraph program {\n    node [shape=box, fontname=monospace];\n\
+         \x20   0 [label=\"0: nop +0\", color=red, penwidth=2];\n\
+         \x20   1 [label=\"1: jmp -1\", color=red, penwidth=2];\n\
+         \x20   2 [label=\"2: acc +1\", style=dashed];\n\
+         \x20   3 [label=\"3: jmp +5\", style=dashed];\n\
+         \x20   4 [label=\"4: acc +1\", style=filled, fillcolor=palegreen];\n\
+         \x20   end [shape=doublecircle];\n\
+         \x20   0 -> 1 [color=red, penwidth=2];\n\
+         \x20   1 -> 0 [color=red, penwidth=2];\n\
+         \x20   2 -> 3;\n\
+         \x20   out3 [label=\"8\", shape=plaintext, fontcolor=red];\n\
+         \x20   3 -> out3 [style=dashed, color=red];\n\
+         \x20   4 -> end;\n}\n",
+        cfg.dot(&cfg.loop_through(0).unwrap())
+    );
+
+    assert!(Cfg::new(&[]).loops().is_empty());
+    assert!(Cfg::new(&[]).unreachable().is_empty());
+}
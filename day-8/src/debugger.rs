@@ -0,0 +1,281 @@
+// A debugger for handheld programs, taking one command per line, so that it
+// can be scripted as well as used at a prompt.
+//
+// The program stops for good when it terminates, loops or jumps out of itself;
+// `patch` an instruction and `reset` to try again.
+use std::collections::BTreeSet;
+use std::io::{self, BufRead, Write};
+
+use crate::machine::Machine;
+use crate::matt::{parse_line, Instruction};
+
+const HELP: &str = "\
+step [n]            run the next n instructions, or 1
+continue            run until a breakpoint, a watched change or the program stops
+break [pc]          stop before running pc, or list the breakpoints
+delete <pc>         remove the breakpoint at pc
+watch acc           stop whenever the accumulator changes
+print [pc|acc]      show where the program is, and how it stopped if it has
+list [pc]           show the instructions around pc, or the next to run
+patch <pc> <instr>  replace the instruction at pc, e.g. `patch 7 nop -4`
+reset               start the program again, keeping patches
+quit                leave the debugger
+";
+
+/// How many instructions `list` shows either side of the one asked for.
+const CONTEXT: usize = 3;
+
+/// A program being debugged.
+pub struct Debugger<'a> {
+    machine: Machine<'a>,
+    breakpoints: BTreeSet<usize>,
+    watch_acc: bool,
+}
+
+impl<'a> Debugger<'a> {
+    /// A debugger about to run the first instruction of `program`.
+    pub fn new(program: &'a [Instruction]) -> Debugger<'a> {
+        Debugger {
+            machine: Machine::new(program),
+            breakpoints: BTreeSet::new(),
+            watch_acc: false,
+        }
+    }
+
+    pub fn machine(&self) -> &Machine<'a> {
+        &self.machine
+    }
+
+    /// Carries out each command read from `input` until it ends or says
+    /// `quit`, writing the replies to `output`. Writes `prompt` before
+    /// reading each command, if there is one.
+    pub fn run<R, W>(&mut self, input: R, mut output: W, prompt: Option<&str>) -> io::Result<()>
+    where
+        R: BufRead,
+        W: Write,
+    {
+        let mut lines = input.lines();
+        loop {
+            if let Some(prompt) = prompt {
+                write!(output, "{}", prompt)?;
+                output.flush()?;
+            }
+            let line = match lines.next() {
+                Some(line) => line?,
+                None => return Ok(()),
+            };
+            let words: Vec<_> = line.split_whitespace().collect();
+            match words.split_first() {
+                None => {}
+                Some((&"quit", _)) | Some((&"q", _)) => return Ok(()),
+                Some((command, args)) => match self.execute(command, args) {
+                    Ok(reply) => write!(output, "{}", reply)?,
+                    Err(e) => writeln!(output, "error: {}", e)?,
+                },
+            }
+        }
+    }
+
+    /// Carries out one command, returning what it has to say.
+    pub fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        let len = self.machine.program().len();
+        match (command, args) {
+            ("step", []) | ("s", []) => Ok(self.advance(Some(1))),
+            ("step", [n]) | ("s", [n]) => Ok(self.advance(Some(number(n, "a number of steps")?))),
+            ("continue", []) | ("c", []) => Ok(self.advance(None)),
+            ("break", []) | ("b", []) if self.breakpoints.is_empty() => {
+                Ok("no breakpoints\n".to_string())
+            }
+            ("break", []) | ("b", []) => {
+                let pcs: Vec<_> = self.breakpoints.iter().map(usize::to_string).collect();
+                Ok(format!("breakpoints at {}\n", pcs.join(", ")))
+            }
+            ("break", [pc]) | ("b", [pc]) => {
+                let pc = self.pc(pc)?;
+                self.breakpoints.insert(pc);
+                Ok(format!("breakpoint at {}\n", pc))
+            }
+            ("delete", [pc]) | ("d", [pc]) => {
+                let pc = number(pc, "a pc")?;
+                match self.breakpoints.remove(&pc) {
+                    true => Ok(format!("deleted the breakpoint at {}\n", pc)),
+                    false => Err(format!("no breakpoint at {}", pc)),
+                }
+            }
+            ("watch", ["acc"]) => {
+                self.watch_acc = true;
+                Ok("watching acc\n".to_string())
+            }
+            ("watch", _) => Err("only acc can be watched".to_string()),
+            ("print", []) | ("p", []) => Ok(self.status()),
+            ("print", ["pc"]) | ("p", ["pc"]) => Ok(format!("pc {}\n", self.machine.pc())),
+            ("print", ["acc"]) | ("p", ["acc"]) => Ok(format!("acc {}\n", self.machine.acc())),
+            ("print", [pc]) | ("p", [pc]) => {
+                let pc = self.pc(pc)?;
+                Ok(format!("{:>4}: {}\n", pc, self.machine.program()[pc]))
+            }
+            ("list", []) | ("l", []) => Ok(self.list(self.machine.pc())),
+            ("list", [pc]) | ("l", [pc]) => Ok(self.list(self.pc(pc)?)),
+            ("patch", [pc, instruction @ ..]) if !instruction.is_empty() => {
+                let pc = self.pc(pc)?;
+                let patched = parse_line(&instruction.join(" ")).map_err(|e| e.to_string())?;
+                let was = self.machine.program()[pc];
+                self.machine.patch(pc, patched);
+                Ok(format!("patched {}: {} -> {}\n", pc, was, patched))
+            }
+            ("reset", []) => {
+                self.machine.reset();
+                Ok(self.location())
+            }
+            ("help", []) | ("h", []) => Ok(HELP.to_string()),
+            ("step", _) | ("s", _) | ("break", _) | ("b", _) | ("list", _) | ("l", _) => {
+                Err(format!("`{}` takes at most one argument", command))
+            }
+            ("continue", _) | ("reset", _) | ("help", _) => {
+                Err(format!("`{}` takes no arguments", command))
+            }
+            ("delete", _) | ("d", _) => Err("usage: delete <pc>".to_string()),
+            ("print", _) | ("p", _) => Err("usage: print [pc|acc]".to_string()),
+            ("patch", _) => Err(format!("usage: patch <pc> <instr>, with pc below {}", len)),
+            _ => Err(format!("unknown command `{}`; try `help`", command)),
+        }
+    }
+
+    /// Runs up to `steps` instructions, or as many as it takes, stopping
+    /// early at a breakpoint, a change to a watched accumulator, or once the
+    /// program stops.
+    fn advance(&mut self, steps: Option<usize>) -> String {
+        let mut reply = String::new();
+        for _ in 0..steps.unwrap_or(usize::MAX) {
+            let acc = self.machine.acc();
+            if let Some(outcome) = self.machine.step().or_else(|| self.machine.outcome()) {
+                return format!("{}stopped: {}\n", reply, outcome);
+            }
+            if self.watch_acc && self.machine.acc() != acc {
+                reply += &format!("acc {} -> {}\n", acc, self.machine.acc());
+                break;
+            }
+            if self.breakpoints.contains(&self.machine.pc()) {
+                reply += &format!("breakpoint at {}\n", self.machine.pc());
+                break;
+            }
+        }
+        reply + &self.location()
+    }
+
+    /// The next instruction to run, with the accumulator.
+    fn location(&self) -> String {
+        let pc = self.machine.pc();
+        let instruction = match self.machine.program().get(pc) {
+            Some(instruction) => instruction.to_string(),
+            None => "end".to_string(),
+        };
+        format!(
+            "{:>4}: {:<10} acc {}\n",
+            pc,
+            instruction,
+            self.machine.acc()
+        )
+    }
+
+    fn status(&self) -> String {
+        let mut status = format!(
+            "pc {}, acc {}, {} steps run\n",
+            self.machine.pc(),
+            self.machine.acc(),
+            self.machine.steps()
+        );
+        if let Some(outcome) = self.machine.outcome() {
+            status += &format!("stopped: {}\n", outcome);
+        }
+        status
+    }
+
+    /// The instructions around `pc`, marking the next to run with `>` and
+    /// breakpoints with `*`.
+    fn list(&self, pc: usize) -> String {
+        let program = self.machine.program();
+        let end = (pc + CONTEXT + 1).min(program.len());
+        (pc.saturating_sub(CONTEXT)..end)
+            .map(|i| {
+                let next = if i == self.machine.pc() { '>' } else { ' ' };
+                let breakpoint = if self.breakpoints.contains(&i) {
+                    '*'
+                } else {
+                    ' '
+                };
+                format!("{}{}{:>4}: {}\n", next, breakpoint, i, program[i])
+            })
+            .collect()
+    }
+
+    /// The pc in `arg`, which must be in the program.
+    fn pc(&self, arg: &str) -> Result<usize, String> {
+        let len = self.machine.program().len();
+        match number(arg, "a pc")? {
+            pc if pc < len => Ok(pc),
+            pc => Err(format!(
+                "{} is past the last instruction, {}",
+                pc,
+                len as isize - 1
+            )),
+        }
+    }
+}
+
+fn number(arg: &str, expected: &str) -> Result<usize, String> {
+    arg.parse()
+        .map_err(|_| format!("expected {}, found `{}`", expected, arg))
+}
+
+#[test]
+fn test_debugger() {
+    use aoc_common::parse;
+
+    let program: Vec<Instruction> = parse::lines(
+        "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n",
+        parse_line,
+    )
+    .unwrap();
+    let session = |commands: &str| {
+        let mut output = vec![];
+        Debugger::new(&program)
+            .run(commands.as_bytes(), &mut output, None)
+            .unwrap();
+        String::from_utf8(output).unwrap()
+    };
+
+    assert_eq!(
+        "   2: jmp +4     acc 1\n\
+         breakpoint at 3\n\
+         breakpoint at 3\n   3: acc +3     acc 2\n\
+         stopped: about to run 1 again, with acc 5\n\
+         pc 1, acc 5, 7 steps run\nstopped: about to run 1 again, with acc 5\n",
+        session("step 2\nbreak 3\ncontinue\ncontinue\nprint\n")
+    );
+    assert_eq!(
+        "patched 7: jmp -4 -> nop -4\n\
+         watching acc\n\
+         acc 0 -> 1\n   2: jmp +4     acc 1\n\
+         acc 1 -> 2\n   7: nop -4     acc 2\n\
+         stopped: terminated with acc 8\n",
+        session("patch 7 nop -4\nwatch acc\ncontinue\ncontinue\nstep 4\n")
+    );
+    assert_eq!(
+        "breakpoint at 6\nbreakpoint at 6\n   6: acc +1     acc 1\n\
+         \x20    3: acc +3\n     4: jmp -3\n     5: acc -99\n>*   6: acc +1\n     7: jmp -4\n     8: acc +6\n",
+        session("b 6\nc\nl\nquit\nc\n")
+    );
+    assert_eq!(
+        "stopped: about to run 1 again, with acc 5\n   0: nop +0     acc 0\nacc 0\n",
+        session("continue\nreset\np acc\n")
+    );
+    assert_eq!(
+        "error: 9 is past the last instruction, 8\n\
+         error: line 1, column 1: expected `acc`, `jmp` or `nop`, found `hop`\n  |\n1 | hop +1\n  | ^^^\n\
+         error: only acc can be watched\n\
+         error: unknown command `run`; try `help`\n\
+         error: expected a number of steps, found `x`\n",
+        session("break 9\npatch 1 hop +1\nwatch pc\nrun\nstep x\n")
+    );
+}
@@ -0,0 +1,119 @@
+// Random boot code that loops forever, unless exactly one instruction is
+// flipped between `jmp` and `nop`.
+//
+// The program runs straight through its first part to a `jmp` back into it,
+// which closes the loop; flipping that `jmp` lets it carry on through the rest
+// of the program to the end. Every other instruction that might be flipped is
+// made to lead back into the loop:
+//
+// * a `nop` on the way to the `jmp`, flipped, jumps to an instruction in the
+//   loop,
+// * a `jmp` on the way only jumps forwards, over instructions that themselves
+//   jump into the loop and which it falls into if flipped,
+// * anything else is never run, flipped or not.
+use aoc_common::generate::{Generated, Rng};
+
+/// A program of `size` instructions, at least 8.
+pub fn input(rng: &mut Rng, size: usize) -> Generated {
+    let size = size.max(8);
+    let fix = rng.range(size / 3..=size * 2 / 3);
+    let mut program = vec![String::new(); size];
+
+    // Lay out the path from the start to the `jmp` to fix, leaving what it
+    // jumps over to be filled in once the loop is known.
+    let (mut path, mut nops) = (vec![], vec![]);
+    let mut looped = 0;
+    let mut i = 0;
+    while i < fix {
+        path.push(i);
+        match rng.range(0..=3u8) {
+            0 | 1 => {
+                let value = rng.range(-50..=50i64);
+                program[i] = format!("acc {:+}", value);
+                looped += value;
+                i += 1;
+            }
+            2 => {
+                nops.push(i);
+                i += 1;
+            }
+            _ => {
+                let offset = rng.range(2..=4).min(fix - i);
+                program[i] = format!("jmp {:+}", offset);
+                i += offset;
+            }
+        }
+    }
+    let start = *rng.choose(&path);
+    program[fix] = format!("jmp {:+}", start as i64 - fix as i64);
+
+    let in_loop: Vec<_> = path.iter().copied().filter(|&i| i >= start).collect();
+    for &i in &nops {
+        program[i] = format!("nop {:+}", *rng.choose(&in_loop) as i64 - i as i64);
+    }
+    for (i, instruction) in program.iter_mut().enumerate().take(fix) {
+        if instruction.is_empty() {
+            *instruction = format!("jmp {:+}", *rng.choose(&in_loop) as i64 - i as i64);
+        }
+    }
+
+    // The rest only runs once the loop is fixed, and only ever moves forwards
+    // to the end. Whatever it jumps over is never run, so can be anything.
+    let mut fixed = looped;
+    let mut i = fix + 1;
+    while i < size {
+        match rng.range(0..=3u8) {
+            0 | 1 => {
+                let value = rng.range(-50..=50i64);
+                program[i] = format!("acc {:+}", value);
+                fixed += value;
+                i += 1;
+            }
+            2 => {
+                let target = rng.range(0..=size - 1) as i64;
+                program[i] = format!("nop {:+}", target - i as i64);
+                i += 1;
+            }
+            _ => {
+                let offset = rng.range(1..=4).min(size - i);
+                program[i] = format!("jmp {:+}", offset);
+                for (skipped, instruction) in
+                    program.iter_mut().enumerate().take(i + offset).skip(i + 1)
+                {
+                    let offset = rng.range(0..=size - 1) as i64 - skipped as i64;
+                    *instruction = match rng.range(0..=2u8) {
+                        0 => format!("acc {:+}", rng.range(-50..=50i64)),
+                        1 => format!("nop {:+}", offset),
+                        _ => format!("jmp {:+}", offset),
+                    };
+                }
+                i += offset;
+            }
+        }
+    }
+
+    let input = program.iter().map(|line| format!("{}\n", line)).collect();
+    Generated::new(input, looped, fixed)
+}
+
+#[test]
+fn test_generate() {
+    use aoc_common::Solver;
+
+    use crate::matt::Matt;
+
+    for seed in 0..20 {
+        let generated = input(&mut Rng::new(seed), 60);
+        assert_eq!(60, generated.input.lines().count());
+        let program = Matt::parse(&generated.input).unwrap();
+        assert_eq!(generated.one, Matt::part_one(&program));
+        assert_eq!(generated.two, Matt::part_two(&program));
+    }
+
+    // The linear repair finds the same flip as trying every one.
+    let generated = input(&mut Rng::new(1), 2000);
+    let program = Matt::parse(&generated.input).unwrap();
+    let repair = crate::matt::part_two(&program);
+    assert_eq!(repair, crate::matt::part_two_brute_force(&program));
+    assert_eq!(generated.two, repair.unwrap().acc.into());
+}
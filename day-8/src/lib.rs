@@ -0,0 +1,23 @@
+// --- Day 8: Handheld Halting ---
+//
+// https://adventofcode.com/2020/day/8
+use aoc_common::Solution;
+
+pub mod cfg;
+pub mod debugger;
+pub mod generate;
+pub mod machine;
+pub mod matt;
+pub mod repair;
+pub mod trace;
+
+/// Every author's solution for the day.
+pub fn solutions() -> Vec<Solution> {
+    vec![Solution::new::<matt::Matt>(8, "matt")]
+}
+
+/// Alternative implementations, which are only run to benchmark them against
+/// each other.
+pub fn variants() -> Vec<Solution> {
+    vec![Solution::new::<matt::BruteForce>(8, "matt").with_variant("brute-force")]
+}
@@ -0,0 +1,241 @@
+// The handheld console, running a program one instruction at a time.
+//
+// Running stops for good as soon as the program counter lands just past the
+// last instruction, lands on an instruction that has already run, or a `jmp`
+// would take it anywhere else outside the program.
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::matt::{Instruction, Sign};
+
+/// How running a program stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The program counter landed just past the last instruction.
+    Terminated { acc: isize },
+    /// The instruction at `pc` was about to run a second time.
+    InfiniteLoop { acc: isize, pc: usize },
+    /// The `jmp` at `pc` would have jumped to `target`, outside the program.
+    JumpOutOfBounds { pc: usize, target: isize },
+    /// The machine ran as many steps as it was allowed to.
+    StepLimitExceeded,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Terminated { acc } => write!(f, "terminated with acc {}", acc),
+            Outcome::InfiniteLoop { acc, pc } => {
+                write!(f, "about to run {} again, with acc {}", pc, acc)
+            }
+            Outcome::JumpOutOfBounds { pc, target } => {
+                write!(f, "{} jumps out of the program, to {}", pc, target)
+            }
+            Outcome::StepLimitExceeded => f.write_str("ran out of steps"),
+        }
+    }
+}
+
+/// A program being run.
+#[derive(Debug, Clone)]
+pub struct Machine<'a> {
+    /// Borrowed until an instruction is patched.
+    program: Cow<'a, [Instruction]>,
+    pc: usize,
+    acc: isize,
+    /// Whether each instruction has run yet.
+    executed: Vec<bool>,
+    steps: usize,
+    step_limit: Option<usize>,
+    /// Set once a `jmp` has tried to leave the program.
+    out_of_bounds: Option<Outcome>,
+}
+
+impl<'a> Machine<'a> {
+    /// A machine about to run the first instruction of `program`, with the
+    /// accumulator at 0.
+    pub fn new(program: &'a [Instruction]) -> Machine<'a> {
+        Machine {
+            program: Cow::Borrowed(program),
+            pc: 0,
+            acc: 0,
+            executed: vec![false; program.len()],
+            steps: 0,
+            step_limit: None,
+            out_of_bounds: None,
+        }
+    }
+
+    /// The same machine, stopping with [`Outcome::StepLimitExceeded`] once it
+    /// has run `limit` instructions.
+    pub fn with_step_limit(self, limit: usize) -> Machine<'a> {
+        Machine {
+            step_limit: Some(limit),
+            ..self
+        }
+    }
+
+    pub fn program(&self) -> &[Instruction] {
+        &self.program
+    }
+
+    /// Replaces the instruction at `pc` with `instruction`, from the next time
+    /// it runs. Panics if `pc` isn't in the program.
+    pub fn patch(&mut self, pc: usize, instruction: Instruction) {
+        self.program.to_mut()[pc] = instruction;
+    }
+
+    /// Starts the program again from the first instruction, with the
+    /// accumulator at 0 and nothing run yet, keeping any patches.
+    pub fn reset(&mut self) {
+        self.pc = 0;
+        self.acc = 0;
+        self.executed = vec![false; self.program.len()];
+        self.steps = 0;
+        self.out_of_bounds = None;
+    }
+
+    /// Where the next instruction to run is.
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    pub fn acc(&self) -> isize {
+        self.acc
+    }
+
+    /// How many instructions have run.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Whether the instruction at `pc` has run yet.
+    pub fn executed(&self, pc: usize) -> bool {
+        self.executed.get(pc).copied().unwrap_or(false)
+    }
+
+    /// How the machine has stopped, if it has.
+    pub fn outcome(&self) -> Option<Outcome> {
+        if self.out_of_bounds.is_some() {
+            self.out_of_bounds
+        } else if self.pc == self.program.len() {
+            Some(Outcome::Terminated { acc: self.acc })
+        } else if self.executed[self.pc] {
+            Some(Outcome::InfiniteLoop {
+                acc: self.acc,
+                pc: self.pc,
+            })
+        } else if self.step_limit.is_some_and(|limit| self.steps >= limit) {
+            Some(Outcome::StepLimitExceeded)
+        } else {
+            None
+        }
+    }
+
+    /// Runs the next instruction, or says how the machine has stopped if it
+    /// already has, or does so now by jumping out of the program.
+    pub fn step(&mut self) -> Option<Outcome> {
+        if let Some(outcome) = self.outcome() {
+            return Some(outcome);
+        }
+
+        let pc = self.pc;
+        self.executed[pc] = true;
+        self.steps += 1;
+        match self.program[pc] {
+            Instruction::Acc(sign, x) => {
+                self.acc += argument(sign, x);
+                self.pc += 1;
+            }
+            Instruction::Jmp(sign, x) => {
+                let target = pc as isize + argument(sign, x);
+                if target < 0 || target > self.program.len() as isize {
+                    self.out_of_bounds = Some(Outcome::JumpOutOfBounds { pc, target });
+                    return self.out_of_bounds;
+                }
+                self.pc = target as usize;
+            }
+            Instruction::Nop(_, _) => self.pc += 1,
+        }
+        None
+    }
+
+    /// Runs instructions until the machine stops.
+    pub fn run(&mut self) -> Outcome {
+        loop {
+            if let Some(outcome) = self.step() {
+                return outcome;
+            }
+        }
+    }
+}
+
+/// An instruction's argument, with its sign.
+pub fn argument(sign: Sign, x: isize) -> isize {
+    match sign {
+        Sign::Positive => x,
+        Sign::Negative => -x,
+    }
+}
+
+#[test]
+fn test_machine() {
+    use aoc_common::parse;
+
+    let program =
+        |input: &str| -> Vec<Instruction> { parse::lines(input, crate::matt::parse_line).unwrap() };
+    let run = |input: &str| Machine::new(&program(input)).run();
+
+    assert_eq!(
+        Outcome::InfiniteLoop { acc: 5, pc: 1 },
+        run("nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n")
+    );
+    assert_eq!(
+        Outcome::Terminated { acc: 8 },
+        run("nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\nnop -4\nacc +6\n")
+    );
+    assert_eq!(Outcome::Terminated { acc: 0 }, run(""));
+    assert_eq!(Outcome::Terminated { acc: 1 }, run("acc +1\njmp +1\n"));
+    assert_eq!(
+        Outcome::JumpOutOfBounds { pc: 1, target: -2 },
+        run("acc +1\njmp -3\n")
+    );
+    assert_eq!(
+        Outcome::JumpOutOfBounds { pc: 0, target: 3 },
+        run("jmp +3\nacc +1\n")
+    );
+
+    let looping = program("acc +1\njmp -1\n");
+    let mut machine = Machine::new(&looping).with_step_limit(1);
+    assert_eq!(None, machine.step());
+    assert_eq!((1, 1, 1), (machine.pc(), machine.acc(), machine.steps()));
+    assert!(machine.executed(0) && !machine.executed(1));
+    assert_eq!(Some(Outcome::StepLimitExceeded), machine.step());
+    assert_eq!(
+        Outcome::InfiniteLoop { acc: 1, pc: 0 },
+        Machine::new(&looping).run()
+    );
+
+    let mut machine = Machine::new(&looping[1..]);
+    assert_eq!(
+        Some(Outcome::JumpOutOfBounds { pc: 0, target: -1 }),
+        machine.step()
+    );
+    assert_eq!(
+        Some(Outcome::JumpOutOfBounds { pc: 0, target: -1 }),
+        machine.step()
+    );
+    assert_eq!(1, machine.steps());
+    assert_eq!(
+        "0 jumps out of the program, to -1",
+        machine.run().to_string()
+    );
+
+    let mut machine = Machine::new(&looping);
+    assert_eq!(Outcome::InfiniteLoop { acc: 1, pc: 0 }, machine.run());
+    machine.patch(1, Instruction::Nop(Sign::Negative, 1));
+    assert_eq!(Outcome::InfiniteLoop { acc: 1, pc: 0 }, machine.run());
+    machine.reset();
+    assert_eq!(Outcome::Terminated { acc: 1 }, machine.run());
+    assert_eq!(looping[1], Instruction::Jmp(Sign::Negative, 1));
+}
@@ -1,69 +1,165 @@
 // --- Day 8: Handheld Halting ---
 //
 // https://adventofcode.com/2020/day/8
-use std::fs::File;
-use std::io::{self, BufRead};
+use std::convert::TryFrom;
+use std::fmt;
 
+use aoc_common::json::Json;
+use aoc_common::{parse, Answer, ParseError, Part, Result, Solver};
+
+use crate::machine::{Machine, Outcome};
+use crate::repair::{self, Repair};
+
+/// Matt's solution.
+pub struct Matt;
+
+/// Matt's solution as it was before finding the flip in linear time: tries
+/// flipping each instruction in turn, running the whole program each time.
+pub struct BruteForce;
+
+impl Solver for Matt {
+    type Input = Program;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::lines(input, parse_line)?)
+    }
+
+    fn part_one(program: &Self::Input) -> Answer {
+        match part_one(program) {
+            Outcome::Terminated { acc } | Outcome::InfiniteLoop { acc, .. } => acc.into(),
+            Outcome::JumpOutOfBounds { .. } | Outcome::StepLimitExceeded => Answer::Unsolved,
+        }
+    }
+
+    fn part_two(program: &Self::Input) -> Answer {
+        repaired(part_two(program))
+    }
+
+    fn auxiliary(program: &Self::Input, part: Part) -> Vec<(&'static str, Json)> {
+        auxiliary(program, part, part_two)
+    }
+}
+
+impl Solver for BruteForce {
+    type Input = Program;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Matt::parse(input)
+    }
+
+    fn part_one(program: &Self::Input) -> Answer {
+        Matt::part_one(program)
+    }
+
+    fn part_two(program: &Self::Input) -> Answer {
+        repaired(part_two_brute_force(program))
+    }
+
+    fn auxiliary(program: &Self::Input, part: Part) -> Vec<(&'static str, Json)> {
+        auxiliary(program, part, part_two_brute_force)
+    }
+}
+
+/// The accumulator once the repaired program terminates, or unsolved if no
+/// one flip makes it.
+fn repaired(repair: Option<Repair>) -> Answer {
+    repair.map_or(Answer::Unsolved, |repair| repair.acc.into())
+}
+
+fn auxiliary(
+    program: &Program,
+    part: Part,
+    part_two: fn(&Program) -> Option<Repair>,
+) -> Vec<(&'static str, Json)> {
+    match part {
+        Part::One => match part_one(program) {
+            Outcome::Terminated { acc } => {
+                vec![("accumulator", acc.into()), ("looped", false.into())]
+            }
+            Outcome::InfiniteLoop { acc, pc } => vec![
+                ("accumulator", acc.into()),
+                ("looped", true.into()),
+                ("pc", pc.into()),
+            ],
+            Outcome::JumpOutOfBounds { pc, target } => {
+                vec![("jumped_from", pc.into()), ("jumped_to", target.into())]
+            }
+            Outcome::StepLimitExceeded => vec![],
+        },
+        Part::Two => match part_two(program) {
+            Some(repair) => vec![("flipped", repair.flipped.into())],
+            None => vec![],
+        },
+    }
+}
+
+/// The sign of an instruction's argument.
 #[derive(Clone, Copy, Debug, PartialEq)]
-enum Sign {
+pub enum Sign {
     Positive,
     Negative,
 }
 
+/// An instruction, and the size of its argument.
 #[derive(Clone, Copy, Debug, PartialEq)]
-enum Instruction {
+pub enum Instruction {
     Acc(Sign, isize),
     Jmp(Sign, isize),
     Nop(Sign, isize),
 }
 
-type Program = Vec<Instruction>;
-
-fn part_one(program: &Program) -> (isize, bool) {
-    let mut acc = 0;
-    let mut will_loop = false;
-    let mut pos = 0;
-    let mut executed = vec![];
-    while !will_loop && pos < program.len() {
-        let ins = &program[pos];
-        if executed.contains(&pos) {
-            will_loop = true;
-            continue;
-        }
-        executed.push(pos);
-        match ins {
-            Instruction::Acc(Sign::Positive, x) => {
-                acc += x;
-                pos += 1;
-            }
-            Instruction::Acc(Sign::Negative, x) => {
-                acc -= x;
-                pos += 1;
-            }
-            Instruction::Jmp(Sign::Positive, x) => pos += *x as usize,
-            Instruction::Jmp(Sign::Negative, x) => pos -= *x as usize,
-            _ => pos += 1,
-        }
+/// An instruction as it's written in a program, e.g. `jmp -4`.
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (op, sign, x) = match self {
+            Instruction::Acc(sign, x) => ("acc", sign, x),
+            Instruction::Jmp(sign, x) => ("jmp", sign, x),
+            Instruction::Nop(sign, x) => ("nop", sign, x),
+        };
+        let sign = match sign {
+            Sign::Positive => '+',
+            Sign::Negative => '-',
+        };
+        write!(f, "{} {}{}", op, sign, x)
     }
-    (acc, will_loop)
 }
-fn part_two(program: &Program) -> isize {
-    let mut nth = 0;
-    loop {
-        let mut prog = program.to_owned();
-        flip_nth_instruction(&mut prog, nth);
-        let (acc, looped) = part_one(&prog);
-        if !looped {
-            return acc;
-        } else {
-            nth += 1;
-        }
-    }
+
+pub type Program = Vec<Instruction>;
+
+/// Runs the program until it stops, which it does at the latest when it's
+/// about to run an instruction a second time.
+pub fn part_one(program: &Program) -> Outcome {
+    Machine::new(program).run()
+}
+
+/// The `jmp` or `nop` to flip to make the program terminate, and the
+/// accumulator once it does, if any one flip does.
+pub fn part_two(program: &Program) -> Option<Repair> {
+    repair::repair(program)
+}
+
+/// The first `jmp` or `nop` whose flip makes the program terminate, found by
+/// flipping each in turn and running the program, and the accumulator once
+/// it terminates. Never flips the last instruction.
+pub fn part_two_brute_force(program: &Program) -> Option<Repair> {
+    (0..)
+        .map_while(|nth| {
+            let mut prog = program.to_owned();
+            let flipped = flip_nth_instruction(&mut prog, nth)?;
+            match part_one(&prog) {
+                Outcome::Terminated { acc } => Some(Some(Repair { flipped, acc })),
+                _ => Some(None),
+            }
+        })
+        .flatten()
+        .next()
 }
 
-fn flip_nth_instruction(program: &mut Program, nth: usize) {
+/// Swaps the `nth` `jmp` or `nop` in the program for the other, returning
+/// where it is, or `None` if there are fewer.
+pub fn flip_nth_instruction(program: &mut Program, nth: usize) -> Option<usize> {
     let mut nth = nth;
-    for idx in 0..program.len() - 1 {
+    for idx in 0..program.len().saturating_sub(1) {
         let ins = program[idx];
         match ins {
             Instruction::Acc(_, _) => {}
@@ -74,51 +170,89 @@ fn flip_nth_instruction(program: &mut Program, nth: usize) {
                         Instruction::Nop(s, i) => program[idx] = Instruction::Jmp(s, i),
                         _ => {}
                     }
-                    return;
+                    return Some(idx);
                 } else {
                     nth -= 1;
                 }
             }
         }
     }
+    None
 }
 
-fn main() {
-    let program = load_program("input.txt").unwrap();
+/// Prints the answers for `input`.
+pub fn main(input: &str) -> Result<()> {
+    let program = Matt::parse(input)?;
 
-    println!("Part One: {} ", part_one(&program).0);
-    println!("Part Two: {} ", part_two(&program));
+    println!("Part One: {} ", Matt::part_one(&program));
+    println!("Part Two: {} ", Matt::part_two(&program));
+    Ok(())
 }
 
-fn parse_line(l: String) -> Instruction {
-    let parts: Vec<_> = l.split(' ').collect();
-    let arg = parts[1];
-    let sign = match &arg[0..1] {
-        "+" => Sign::Positive,
-        "-" => Sign::Negative,
-        _ => unreachable!(),
+/// Parses an instruction like `jmp -4`.
+pub fn parse_line(l: &str) -> Result<Instruction, ParseError> {
+    let (op, arg) = parse::split_once(l, l, " ", "`<operation> <argument>`")?;
+    let sign = match arg.get(0..1) {
+        Some("+") => Sign::Positive,
+        Some("-") => Sign::Negative,
+        _ => return Err(ParseError::at(l, arg, "`+` or `-`")),
     };
-    let offset: isize = arg[1..].parse().unwrap();
-    match parts[0] {
-        "acc" => Instruction::Acc(sign, offset),
-        "jmp" => Instruction::Jmp(sign, offset),
-        "nop" => Instruction::Nop(sign, offset),
-        _ => unreachable!(),
+    let digits = &arg[1..];
+    let offset = parse::value::<usize>(l, digits, "an unsigned number")?;
+    let offset = isize::try_from(offset)
+        .map_err(|_| ParseError::at(l, digits, &format!("a number up to {}", isize::MAX)))?;
+    match op {
+        "acc" => Ok(Instruction::Acc(sign, offset)),
+        "jmp" => Ok(Instruction::Jmp(sign, offset)),
+        "nop" => Ok(Instruction::Nop(sign, offset)),
+        _ => Err(ParseError::at(l, op, "`acc`, `jmp` or `nop`")),
     }
 }
-fn load_program(fname: &str) -> io::Result<Program> {
-    let file = File::open(fname)?;
-    let buf = io::BufReader::new(file);
-    let mut program: Program = Vec::new();
-    for line in buf.lines() {
-        program.push(parse_line(line?))
-    }
-    Ok(program)
+
+#[test]
+fn test_parse_error() {
+    let e = Matt::parse("nop +0\nacc +1\nhop -3\n").err().unwrap();
+    assert_eq!(
+        "invalid input: line 3, column 1: expected `acc`, `jmp` or `nop`, found `hop`\n  |\n3 | hop -3\n  | ^^^",
+        e.to_string()
+    );
+    assert_eq!("jmp -4", parse_line("jmp -4").unwrap().to_string());
+    let e = parse_line("jmp 4").unwrap_err();
+    assert_eq!((5, "4"), (e.column, e.found.as_str()));
+
+    // Arguments too big for an `isize` used to wrap round to negative ones.
+    assert_eq!(
+        Instruction::Acc(Sign::Negative, isize::MAX),
+        parse_line(&format!("acc -{}", isize::MAX)).unwrap()
+    );
+    let e = parse_line(&format!("jmp +{}", usize::MAX)).unwrap_err();
+    assert_eq!(
+        (6, format!("a number up to {}", isize::MAX)),
+        (e.column, e.expected)
+    );
 }
 
 #[test]
-fn test() {
-    let program = load_program("test.txt").unwrap();
-    assert_eq!(5, part_one(&program).0);
-    assert_eq!(8, part_two(&program));
+fn test_example() {
+    let program =
+        Matt::parse("nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n")
+            .unwrap();
+    assert_eq!(Outcome::InfiniteLoop { acc: 5, pc: 1 }, part_one(&program));
+    let repair = Some(Repair { flipped: 7, acc: 8 });
+    assert_eq!(repair, part_two(&program));
+    assert_eq!(repair, part_two_brute_force(&program));
+
+    // Jumping back before the first instruction used to underflow.
+    let program = Matt::parse("nop +0\njmp -2\n").unwrap();
+    assert_eq!(
+        Outcome::JumpOutOfBounds { pc: 1, target: -1 },
+        part_one(&program)
+    );
+    assert_eq!(Answer::Unsolved, Matt::part_one(&program));
+    assert_eq!(Answer::Unsolved, BruteForce::part_one(&program));
+    let program = Matt::parse("jmp +0\njmp -1\n").unwrap();
+    assert_eq!(None, part_two(&program));
+    assert_eq!(None, part_two_brute_force(&program));
+    assert_eq!(Answer::Unsolved, Matt::part_two(&program));
+    assert_eq!(Answer::Unsolved, BruteForce::part_two(&program));
 }
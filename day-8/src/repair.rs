@@ -0,0 +1,121 @@
+// Finding the one `jmp` or `nop` to flip so that a looping program terminates,
+// in time linear in the length of the program.
+//
+// Every instruction leads to exactly one other, so the instructions that
+// terminate the program are those from which following the program leads to
+// the end, found by walking backwards from the end once. The program loops, so
+// none of the instructions it runs before looping are among them; flipping one
+// of those fixes the program when the flipped instruction leads to one of
+// them instead. The instructions that follow can't lead back to the flipped
+// one, as it doesn't lead to the end unflipped.
+use crate::machine::{argument, Machine, Outcome};
+use crate::matt::Instruction;
+
+/// A flip that makes a program terminate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Repair {
+    /// Which instruction was flipped between `jmp` and `nop`.
+    pub flipped: usize,
+    /// The accumulator once the repaired program terminates.
+    pub acc: isize,
+}
+
+/// The instruction `instruction` becomes when flipped, unless it's an `acc`.
+pub fn flip(instruction: Instruction) -> Option<Instruction> {
+    match instruction {
+        Instruction::Acc(_, _) => None,
+        Instruction::Jmp(sign, x) => Some(Instruction::Nop(sign, x)),
+        Instruction::Nop(sign, x) => Some(Instruction::Jmp(sign, x)),
+    }
+}
+
+/// Where the program goes after `instruction` at `pc`, if that's in the
+/// program or just past its end.
+fn next(program: &[Instruction], pc: usize, instruction: Instruction) -> Option<usize> {
+    let next = match instruction {
+        Instruction::Jmp(sign, x) => pc as isize + argument(sign, x),
+        _ => pc as isize + 1,
+    };
+    if (0..=program.len() as isize).contains(&next) {
+        Some(next as usize)
+    } else {
+        None
+    }
+}
+
+/// Whether following the program from each instruction leads to the end,
+/// with one more entry for the end itself.
+pub fn terminating(program: &[Instruction]) -> Vec<bool> {
+    let mut from = vec![vec![]; program.len() + 1];
+    for (pc, &instruction) in program.iter().enumerate() {
+        if let Some(next) = next(program, pc, instruction) {
+            from[next].push(pc);
+        }
+    }
+
+    let mut terminating = vec![false; program.len() + 1];
+    terminating[program.len()] = true;
+    let mut stack = vec![program.len()];
+    while let Some(pc) = stack.pop() {
+        for &previous in &from[pc] {
+            if !terminating[previous] {
+                terminating[previous] = true;
+                stack.push(previous);
+            }
+        }
+    }
+    terminating
+}
+
+/// The first flip, in the order the program runs, that makes it terminate.
+/// `None` if the program terminates already, or no one flip makes it.
+pub fn repair(program: &[Instruction]) -> Option<Repair> {
+    let terminating = terminating(program);
+    let mut machine = Machine::new(program);
+    while machine.outcome().is_none() {
+        let pc = machine.pc();
+        let fixed = flip(program[pc])
+            .and_then(|flipped| next(program, pc, flipped))
+            .is_some_and(|next| terminating[next]);
+        if fixed {
+            let mut program = program.to_vec();
+            program[pc] = flip(program[pc]).unwrap();
+            return match Machine::new(&program).run() {
+                Outcome::Terminated { acc } => Some(Repair { flipped: pc, acc }),
+                outcome => unreachable!("the repaired program {}", outcome),
+            };
+        }
+        machine.step();
+    }
+    None
+}
+
+#[test]
+fn test_repair() {
+    use aoc_common::parse;
+
+    let program =
+        |input: &str| -> Vec<Instruction> { parse::lines(input, crate::matt::parse_line).unwrap() };
+
+    let example =
+        program("nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n");
+    assert_eq!(Some(Repair { flipped: 7, acc: 8 }), repair(&example));
+    assert_eq!(
+        vec![false, false, false, false, false, false, false, false, true, true],
+        terminating(&example)
+    );
+
+    // Only the last instruction needs flipping.
+    assert_eq!(
+        Some(Repair { flipped: 1, acc: 1 }),
+        repair(&program("acc +1\njmp -1\n"))
+    );
+    // A `nop` pointing out of the program can't be the fix.
+    assert_eq!(
+        Some(Repair { flipped: 2, acc: 0 }),
+        repair(&program("nop -5\nnop +0\njmp -2\n"))
+    );
+    assert_eq!(None, repair(&program("jmp +0\njmp -1\n")));
+    assert_eq!(None, repair(&program("acc +1\n")));
+    assert_eq!(None, repair(&program("")));
+}
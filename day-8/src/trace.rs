@@ -0,0 +1,383 @@
+// Every instruction a program runs, recorded to compare two runs of it, like
+// a looping program and the same program with one instruction flipped.
+//
+// A trace is written one line per instruction run, then one line saying how
+// the program stopped. As text, lines are tab-separated:
+//
+//     5	7	jmp -4	2	2
+//     loop	1	5
+//
+// giving each step's number, pc, instruction and the accumulator before and
+// after, then one of `terminated <acc>`, `loop <pc> <acc>`,
+// `out-of-bounds <pc> <target>` or `step-limit`. As JSON, each line is an
+// object with those fields named, e.g. `{"outcome":"loop","pc":1,"acc":5}`.
+// Lines starting with `#` are comments.
+use std::fmt;
+use std::io::{self, Write};
+
+use aoc_common::json::Json;
+use aoc_common::report::Format;
+use aoc_common::{parse, ParseError};
+
+use crate::machine::{Machine, Outcome};
+use crate::matt::{parse_line as parse_instruction, Instruction};
+
+/// The fields of a step, in the order they're written.
+const STEP: &[&str] = &["step", "pc", "instruction", "acc_before", "acc_after"];
+
+/// One instruction run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Step {
+    /// How many instructions ran before this one.
+    pub step: usize,
+    pub pc: usize,
+    pub instruction: Instruction,
+    pub acc_before: isize,
+    pub acc_after: isize,
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "step {}, {}: {}, acc {} -> {}",
+            self.step, self.pc, self.instruction, self.acc_before, self.acc_after
+        )
+    }
+}
+
+/// Every instruction a program ran, and how it stopped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trace {
+    pub steps: Vec<Step>,
+    pub outcome: Outcome,
+}
+
+impl Trace {
+    /// Runs `machine` until it stops, recording each instruction it runs.
+    pub fn record(machine: &mut Machine) -> Trace {
+        let mut steps = vec![];
+        while machine.outcome().is_none() {
+            let acc_before = machine.acc();
+            let pc = machine.pc();
+            let instruction = machine.program()[pc];
+            let step = machine.steps();
+            machine.step();
+            steps.push(Step {
+                step,
+                pc,
+                instruction,
+                acc_before,
+                acc_after: machine.acc(),
+            });
+        }
+        Trace {
+            steps,
+            outcome: machine.outcome().unwrap(),
+        }
+    }
+
+    pub fn write<W: Write>(&self, mut output: W, format: Format) -> io::Result<()> {
+        for step in &self.steps {
+            match format {
+                Format::Text => writeln!(
+                    output,
+                    "{}\t{}\t{}\t{}\t{}",
+                    step.step, step.pc, step.instruction, step.acc_before, step.acc_after
+                )?,
+                Format::Json => writeln!(
+                    output,
+                    "{}",
+                    Json::object(vec![
+                        ("step", step.step.into()),
+                        ("pc", step.pc.into()),
+                        ("instruction", step.instruction.to_string().into()),
+                        ("acc_before", step.acc_before.into()),
+                        ("acc_after", step.acc_after.into()),
+                    ])
+                )?,
+            }
+        }
+
+        let (kind, fields) = outcome_fields(self.outcome);
+        match format {
+            Format::Text => {
+                let values: Vec<_> = fields.iter().map(|(_, v)| format!("\t{}", v)).collect();
+                writeln!(output, "{}{}", kind, values.concat())
+            }
+            Format::Json => {
+                let mut object = vec![("outcome", kind.into())];
+                object.extend(fields.into_iter().map(|(k, v)| (k, v.into())));
+                writeln!(output, "{}", Json::object(object))
+            }
+        }
+    }
+
+    /// Reads back a trace as [`Trace::write`] writes it, in either format.
+    pub fn parse(input: &str) -> Result<Trace, ParseError> {
+        let mut steps = vec![];
+        let mut outcome = None;
+        for (i, line) in input.lines().enumerate() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if outcome.is_some() {
+                return Err(ParseError::at(line, line, "nothing after the outcome").offset_lines(i));
+            }
+            match parse_line(line).map_err(|e| e.offset_lines(i))? {
+                Ok(step) => steps.push(step),
+                Err(end) => outcome = Some(end),
+            }
+        }
+        match outcome {
+            Some(outcome) => Ok(Trace { steps, outcome }),
+            None => Err(ParseError::missing(
+                input,
+                "a line saying how the program stopped",
+            )),
+        }
+    }
+}
+
+/// Names `outcome` and its values, as a trace writes them.
+fn outcome_fields(outcome: Outcome) -> (&'static str, Vec<(&'static str, isize)>) {
+    match outcome {
+        Outcome::Terminated { acc } => ("terminated", vec![("acc", acc)]),
+        Outcome::InfiniteLoop { acc, pc } => ("loop", vec![("pc", pc as isize), ("acc", acc)]),
+        Outcome::JumpOutOfBounds { pc, target } => (
+            "out-of-bounds",
+            vec![("pc", pc as isize), ("target", target)],
+        ),
+        Outcome::StepLimitExceeded => ("step-limit", vec![]),
+    }
+}
+
+/// A step, or else how the program stopped.
+fn parse_line(line: &str) -> Result<Result<Step, Outcome>, ParseError> {
+    let fields = fields(line)?;
+    let field = |name: &str| {
+        fields
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| *value)
+            .ok_or_else(|| ParseError::missing(line, &format!("`{}`", name)))
+    };
+    let number = |name: &str| parse::value::<isize>(line, field(name)?, "a number");
+    let pc = || parse::value::<usize>(line, field("pc")?, "a pc");
+
+    let kind = match field("outcome") {
+        Ok(kind) => kind,
+        Err(_) => {
+            let instruction = field("instruction")?;
+            return Ok(Ok(Step {
+                step: parse::value(line, field("step")?, "a step number")?,
+                pc: pc()?,
+                instruction: parse_instruction(instruction)
+                    .map_err(|e| ParseError::at(line, instruction, &e.expected))?,
+                acc_before: number("acc_before")?,
+                acc_after: number("acc_after")?,
+            }));
+        }
+    };
+    Ok(Err(match kind {
+        "terminated" => Outcome::Terminated {
+            acc: number("acc")?,
+        },
+        "loop" => Outcome::InfiniteLoop {
+            acc: number("acc")?,
+            pc: pc()?,
+        },
+        "out-of-bounds" => Outcome::JumpOutOfBounds {
+            pc: pc()?,
+            target: number("target")?,
+        },
+        "step-limit" => Outcome::StepLimitExceeded,
+        _ => {
+            return Err(ParseError::at(
+                line,
+                kind,
+                "`terminated`, `loop`, `out-of-bounds` or `step-limit`",
+            ))
+        }
+    }))
+}
+
+/// The names and values on a line, each value a slice of it. Only reads
+/// the flat JSON objects a trace is written as, whose strings never hold
+/// commas or quotes.
+fn fields(line: &str) -> Result<Vec<(&str, &str)>, ParseError> {
+    if let Some(object) = line.strip_prefix('{') {
+        let object = object
+            .strip_suffix('}')
+            .ok_or_else(|| ParseError::missing(line, "`}`"))?;
+        return object
+            .split(',')
+            .map(|field| {
+                let (key, value) = parse::split_once(line, field, ":", "`\"name\":value`")?;
+                Ok((unquote(key), unquote(value)))
+            })
+            .collect();
+    }
+
+    let columns: Vec<_> = line.split('\t').collect();
+    let names: &[&str] = match columns[0] {
+        "terminated" => &["outcome", "acc"],
+        "loop" => &["outcome", "pc", "acc"],
+        "out-of-bounds" => &["outcome", "pc", "target"],
+        "step-limit" => &["outcome"],
+        _ => STEP,
+    };
+    if columns.len() != names.len() {
+        return Err(ParseError::at(
+            line,
+            line,
+            &format!(
+                "{} tab-separated columns: {}",
+                names.len(),
+                names.join(", ")
+            ),
+        ));
+    }
+    Ok(names.iter().copied().zip(columns).collect())
+}
+
+fn unquote(s: &str) -> &str {
+    let s = s.trim();
+    s.strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .unwrap_or(s)
+}
+
+/// Where two traces part ways.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Divergence<'a> {
+    /// The first step that differs.
+    pub step: usize,
+    /// What each trace ran at that step, unless it had stopped.
+    pub left: Option<&'a Step>,
+    pub right: Option<&'a Step>,
+}
+
+/// How two traces compare.
+#[derive(Debug, Clone, Copy)]
+pub struct Diff<'a> {
+    pub left: &'a Trace,
+    pub right: &'a Trace,
+    /// `None` if the traces ran the same steps.
+    pub divergence: Option<Divergence<'a>>,
+}
+
+impl<'a> Diff<'a> {
+    pub fn new(left: &'a Trace, right: &'a Trace) -> Diff<'a> {
+        let len = left.steps.len().max(right.steps.len());
+        let divergence = (0..len)
+            .find(|&i| left.steps.get(i) != right.steps.get(i))
+            .map(|step| Divergence {
+                step,
+                left: left.steps.get(step),
+                right: right.steps.get(step),
+            });
+        Diff {
+            left,
+            right,
+            divergence,
+        }
+    }
+}
+
+impl fmt::Display for Diff<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, trace) in &[("left", self.left), ("right", self.right)] {
+            writeln!(
+                f,
+                "{:<5}  ran {} steps, {}",
+                name,
+                trace.steps.len(),
+                trace.outcome
+            )?;
+        }
+        let divergence = match self.divergence {
+            Some(divergence) => divergence,
+            None if self.left.outcome == self.right.outcome => {
+                return writeln!(f, "The traces are the same")
+            }
+            None => return writeln!(f, "The traces run the same steps, but stop differently"),
+        };
+        writeln!(f, "First difference at step {}:", divergence.step)?;
+        for (name, step) in &[("left", divergence.left), ("right", divergence.right)] {
+            match step {
+                Some(step) => writeln!(f, "{:<5}  {}", name, step)?,
+                None => writeln!(f, "{:<5}  stopped", name)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_trace() {
+    use crate::repair::flip;
+
+    let mut program: Vec<Instruction> = parse::lines(
+        "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n",
+        parse_instruction,
+    )
+    .unwrap();
+    let looping = Trace::record(&mut Machine::new(&program));
+    assert_eq!(7, looping.steps.len());
+    assert_eq!(Outcome::InfiniteLoop { acc: 5, pc: 1 }, looping.outcome);
+    assert_eq!(
+        Step {
+            step: 3,
+            pc: 6,
+            instruction: program[6],
+            acc_before: 1,
+            acc_after: 2
+        },
+        looping.steps[3]
+    );
+
+    program[7] = flip(program[7]).unwrap();
+    let repaired = Trace::record(&mut Machine::new(&program));
+    assert_eq!(Outcome::Terminated { acc: 8 }, repaired.outcome);
+
+    for format in &[Format::Text, Format::Json] {
+        for trace in &[&looping, &repaired] {
+            let mut written = vec![];
+            trace.write(&mut written, *format).unwrap();
+            let written = String::from_utf8(written).unwrap();
+            assert_eq!(**trace, Trace::parse(&written).unwrap(), "{}", written);
+        }
+    }
+    let mut written = vec![];
+    looping.write(&mut written, Format::Json).unwrap();
+    assert_eq!(
+        "{\"step\":0,\"pc\":0,\"instruction\":\"nop +0\",\"acc_before\":0,\"acc_after\":0}",
+        String::from_utf8(written).unwrap().lines().next().unwrap()
+    );
+
+    assert_eq!(
+        "left   ran 7 steps, about to run 1 again, with acc 5\n\
+         right  ran 6 steps, terminated with acc 8\n\
+         First difference at step 4:\n\
+         left   step 4, 7: jmp -4, acc 2 -> 2\n\
+         right  step 4, 7: nop -4, acc 2 -> 2\n",
+        Diff::new(&looping, &repaired).to_string()
+    );
+    assert_eq!(None, Diff::new(&looping, &looping).divergence);
+    let prefix = Trace {
+        steps: looping.steps[..2].to_vec(),
+        outcome: Outcome::StepLimitExceeded,
+    };
+    let diff = Diff::new(&prefix, &looping);
+    assert_eq!(Some((2, None)), diff.divergence.map(|d| (d.step, d.left)));
+
+    let e = Trace::parse("0\t0\tnop +0\t0\t0\n1\t1\thop +1\t0\t1\n").unwrap_err();
+    assert_eq!((2, 5, "hop +1"), (e.line, e.column, e.found.as_str()));
+    let e = Trace::parse("0\t0\tnop +0\t0\t0\n").unwrap_err();
+    assert_eq!("a line saying how the program stopped", e.expected);
+    let e = Trace::parse("{\"outcome\":\"looped\",\"pc\":1}").unwrap_err();
+    assert_eq!("looped", e.found);
+    assert!(Trace::parse("terminated\t8\nterminated\t8\n").is_err());
+    assert!(Trace::parse("loop\t1\n").is_err());
+}